@@ -1,50 +1,4 @@
-#[derive(Debug, Eq, PartialEq)]
-pub enum AffineCipherError {
-    NotCoprime(i32),
-}
-
-pub fn encode(plaintext: &str, a: i32, b: i32) -> Result<String, AffineCipherError> {
-    match find_mmi(a, 26) {
-        None => Err(AffineCipherError::NotCoprime(a)),
-        Some(_) => Ok(plaintext
-            .to_lowercase()
-            .chars()
-            .filter(|c| c.is_ascii_alphanumeric())
-            .map(|c| {
-                if c.is_ascii_digit() {
-                    c
-                } else {
-                    char::from((a * (c as u8 - b'a') as i32 + b).rem_euclid(26) as u8 + b'a')
-                }
-            })
-            .collect::<Vec<char>>()
-            .chunks(5)
-            .map(String::from_iter)
-            .collect::<Vec<String>>()
-            .join(" ")),
-    }
-}
-
-pub fn decode(ciphertext: &str, a: i32, b: i32) -> Result<String, AffineCipherError> {
-    match find_mmi(a, 26) {
-        None => Err(AffineCipherError::NotCoprime(a)),
-        Some(x) => Ok(ciphertext
-            .chars()
-            .filter(|c| !c.is_whitespace())
-            .map(|c| {
-                if c.is_ascii_digit() {
-                    c
-                } else {
-                    char::from(((x * ((c as u8 - b'a') as i32 - b)).rem_euclid(26)) as u8 + b'a')
-                }
-            })
-            .collect()),
-    }
-}
-
-fn find_mmi(a: i32, m: i32) -> Option<i32> {
-    (1..m).find(|n| a * n % m == 1)
-}
+use exercism_rs::cipher::affine::{decode, encode};
 
 fn main() {
     println!("{:?}", encode("Testing,1 2 3, testing.", 3, 4));
@@ -57,7 +11,8 @@ fn main() {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::AffineCipherError::NotCoprime;
+    use exercism_rs::cipher::affine::AffineCipherError::NotCoprime;
+    use exercism_rs::cipher::affine::{AffineCipherError, Alphabet, decode_with, encode_with};
     #[test]
     fn encode_yes() {
         let phrase = "yes";
@@ -186,4 +141,74 @@ mod test {
         let expected = Err(NotCoprime(13));
         assert_eq!(output, expected);
     }
+    #[test]
+    fn lowercase_alphabet_matches_encode() {
+        let output = encode_with("thequickbrownfox", 19, 13, &Alphabet::lowercase());
+        let expected = encode("thequickbrownfox", 19, 13).map(|s| s.replace(' ', ""));
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn alphanumeric_alphabet_round_trips() {
+        let alphabet = Alphabet::alphanumeric();
+        let encoded = encode_with("Testing123", 5, 17, &alphabet).unwrap();
+        assert_eq!(
+            decode_with(&encoded, 5, 17, &alphabet),
+            Ok("Testing123".to_string())
+        );
+    }
+    #[test]
+    fn printable_ascii_alphabet_round_trips() {
+        let alphabet = Alphabet::printable_ascii();
+        let plaintext = "Hello, World! (a + b) * c ~ 42";
+        let encoded = encode_with(plaintext, 2, 3, &alphabet).unwrap();
+        assert_ne!(encoded, plaintext);
+        assert_eq!(
+            decode_with(&encoded, 2, 3, &alphabet),
+            Ok(plaintext.to_string())
+        );
+    }
+    #[test]
+    fn unicode_alphabet() {
+        let alphabet = Alphabet::new("αβγδεζη").unwrap();
+        assert_eq!(encode_with("αβγ", 3, 1, &alphabet), Ok("βεα".to_string()));
+        assert_eq!(decode_with("βεα", 3, 1, &alphabet), Ok("αβγ".to_string()));
+    }
+    #[test]
+    fn coprimality_is_checked_against_alphabet_size() {
+        let alphabet = Alphabet::alphanumeric();
+        assert_eq!(encode_with("abc", 31, 1, &alphabet), Err(NotCoprime(31)));
+        assert!(encode_with("abc", 13, 1, &alphabet).is_ok());
+    }
+    #[test]
+    fn char_outside_alphabet_is_an_error() {
+        assert_eq!(
+            encode_with("ab c", 5, 7, &Alphabet::lowercase()),
+            Err(AffineCipherError::CharNotInAlphabet(' '))
+        );
+    }
+    #[test]
+    fn encode_and_decode_drop_chars_outside_the_alphabet() {
+        assert_eq!(encode("café", 5, 7), encode("caf", 5, 7));
+        assert_eq!(encode("“yes” — no", 5, 7), encode("yes no", 5, 7));
+        assert_eq!(decode("xbt!", 5, 7), Ok("yes".to_string()));
+        assert_eq!(
+            decode_with("xbt!", 5, 7, &Alphabet::lowercase()),
+            Err(AffineCipherError::CharNotInAlphabet('!'))
+        );
+    }
+    #[test]
+    fn decode_ignores_case() {
+        assert_eq!(decode("TYTGN FJR", 3, 7), decode("tytgn fjr", 3, 7));
+    }
+    #[test]
+    fn invalid_alphabets() {
+        assert_eq!(
+            Alphabet::new("").unwrap_err(),
+            AffineCipherError::EmptyAlphabet
+        );
+        assert_eq!(
+            Alphabet::new("abca").unwrap_err(),
+            AffineCipherError::DuplicateChar('a')
+        );
+    }
 }
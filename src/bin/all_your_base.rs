@@ -3,6 +3,11 @@ pub enum Error {
     InvalidInputBase,
     InvalidOutputBase,
     InvalidDigit(u32),
+    /// A character outside the alphabet, with its position in characters.
+    InvalidCharacter {
+        character: char,
+        position: usize,
+    },
 }
 
 pub fn convert(number: &[u32], from_base: u32, to_base: u32) -> Result<Vec<u32>, Error> {
@@ -14,33 +19,127 @@ pub fn convert(number: &[u32], from_base: u32, to_base: u32) -> Result<Vec<u32>,
         return Err(Error::InvalidOutputBase);
     }
 
-    let invalid = number.iter().find(|&n| *n >= from_base);
-    if invalid.is_some() {
-        return Err(Error::InvalidDigit(invalid.cloned().unwrap()));
+    if let Some(&invalid) = number.iter().find(|&n| *n >= from_base) {
+        return Err(Error::InvalidDigit(invalid));
     }
 
-    let mut digit = number
-        .iter()
-        .rev()
-        .enumerate()
-        .fold(0, |mut n, (idx, &curr)| {
-            n += curr * from_base.pow(idx as u32);
-            n
-        });
-
+    // Repeatedly divide the whole digit vector by `to_base`; each remainder
+    // is the next output digit, least significant first.
+    let start = number.iter().position(|&d| d != 0).unwrap_or(number.len());
+    let mut dividend = number[start..].to_vec();
     let mut output = vec![];
 
-    while digit >= to_base {
-        output.push(digit % to_base);
-        digit /= to_base;
+    while !dividend.is_empty() {
+        let mut quotient = Vec::with_capacity(dividend.len());
+        let mut remainder = 0u64;
+        for &digit in &dividend {
+            let acc = remainder * from_base as u64 + digit as u64;
+            let q = (acc / to_base as u64) as u32;
+            if q != 0 || !quotient.is_empty() {
+                quotient.push(q);
+            }
+            remainder = acc % to_base as u64;
+        }
+        output.push(remainder as u32);
+        dividend = quotient;
     }
 
-    output.push(digit);
+    if output.is_empty() {
+        output.push(0);
+    }
     output.reverse();
 
     Ok(output)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: &'static str,
+    case_insensitive: bool,
+    /// Extra symbols accepted when decoding, with the digit each stands for.
+    aliases: &'static [(char, u32)],
+}
+
+impl Alphabet {
+    pub const BINARY: Alphabet = Alphabet::new("01", false);
+    pub const HEX: Alphabet = Alphabet::new("0123456789abcdef", true);
+    pub const BASE36: Alphabet = Alphabet::new("0123456789abcdefghijklmnopqrstuvwxyz", true);
+    /// Bitcoin's alphabet, which drops `0`, `O`, `I` and `l`.
+    pub const BASE58: Alphabet = Alphabet::new(
+        "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+        false,
+    );
+    pub const BASE62: Alphabet = Alphabet::new(
+        "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+        false,
+    );
+    /// Crockford's base32, which drops `I`, `L`, `O` and `U` and reads
+    /// `I`/`L` as 1 and `O` as 0.
+    pub const CROCKFORD32: Alphabet = Alphabet::new("0123456789ABCDEFGHJKMNPQRSTVWXYZ", true)
+        .with_aliases(&[('O', 0), ('I', 1), ('L', 1)]);
+
+    /// `symbols` must be ASCII and free of duplicates; a case-insensitive
+    /// alphabet must not contain both cases of a letter.
+    pub const fn new(symbols: &'static str, case_insensitive: bool) -> Self {
+        Alphabet {
+            symbols,
+            case_insensitive,
+            aliases: &[],
+        }
+    }
+
+    /// Also decode each aliased symbol as its digit; aliases are never
+    /// produced by `encode`.
+    pub const fn with_aliases(self, aliases: &'static [(char, u32)]) -> Self {
+        Alphabet { aliases, ..self }
+    }
+
+    pub fn base(&self) -> u32 {
+        self.symbols.len() as u32
+    }
+
+    fn value(&self, c: char) -> Option<u32> {
+        let matches = |s: char| match self.case_insensitive {
+            true => s.eq_ignore_ascii_case(&c),
+            false => s == c,
+        };
+        if let Some(value) = self.symbols.chars().position(matches) {
+            return Some(value as u32);
+        }
+        self.aliases
+            .iter()
+            .find(|&&(alias, _)| matches(alias))
+            .map(|&(_, value)| value)
+    }
+
+    pub fn decode(&self, s: &str) -> Result<Vec<u32>, Error> {
+        s.chars()
+            .enumerate()
+            .map(|(position, character)| {
+                self.value(character).ok_or(Error::InvalidCharacter {
+                    character,
+                    position,
+                })
+            })
+            .collect()
+    }
+
+    pub fn encode(&self, digits: &[u32]) -> String {
+        let symbols = self.symbols.as_bytes();
+        digits
+            .iter()
+            .map(|&d| symbols[d as usize] as char)
+            .collect()
+    }
+}
+
+/// Re-encode `s` from one alphabet to another, e.g. hex to base58.
+pub fn convert_str(s: &str, from: Alphabet, to: Alphabet) -> Result<String, Error> {
+    let digits = from.decode(s)?;
+    let converted = convert(&digits, from.base(), to.base())?;
+    Ok(to.encode(&converted))
+}
+
 fn main() {}
 
 #[cfg(test)]
@@ -228,4 +327,83 @@ mod test {
             Err(Error::InvalidOutputBase)
         );
     }
+    #[test]
+    fn numbers_wider_than_128_bits() {
+        let input_digits = &[1; 60];
+        let output_digits = convert(input_digits, 10, 16).unwrap();
+        assert_eq!(convert(&output_digits, 16, 10), Ok(input_digits.to_vec()));
+        assert_eq!(output_digits.len(), 50);
+    }
+    #[test]
+    fn hex_is_case_insensitive() {
+        assert_eq!(
+            convert_str("FF", Alphabet::HEX, Alphabet::BINARY),
+            Ok("11111111".to_string())
+        );
+        assert_eq!(
+            convert_str("dEaDbEeF", Alphabet::HEX, Alphabet::BASE36),
+            Ok("1ps9wxb".to_string())
+        );
+    }
+    #[test]
+    fn base58_bitcoin_alphabet() {
+        assert_eq!(
+            convert_str("Hello World", Alphabet::BASE58, Alphabet::HEX),
+            Err(Error::InvalidCharacter {
+                character: 'l',
+                position: 2
+            })
+        );
+        assert_eq!(
+            convert_str("ff", Alphabet::HEX, Alphabet::BASE58),
+            Ok("5Q".to_string())
+        );
+        assert_eq!(
+            convert_str("0", Alphabet::BASE58, Alphabet::HEX),
+            Err(Error::InvalidCharacter {
+                character: '0',
+                position: 0
+            })
+        );
+    }
+    #[test]
+    fn base62_is_case_sensitive() {
+        assert_eq!(
+            convert_str("z", Alphabet::BASE62, Alphabet::HEX),
+            Ok("3d".to_string())
+        );
+        assert_eq!(
+            convert_str("Z", Alphabet::BASE62, Alphabet::HEX),
+            Ok("23".to_string())
+        );
+    }
+    #[test]
+    fn crockford_reads_confusable_letters() {
+        assert_eq!(
+            convert_str("1o", Alphabet::CROCKFORD32, Alphabet::HEX),
+            Ok("20".to_string())
+        );
+        assert_eq!(Alphabet::CROCKFORD32.decode("iLO"), Ok(vec![1, 1, 0]));
+        assert_eq!(
+            Alphabet::CROCKFORD32.decode("1U"),
+            Err(Error::InvalidCharacter {
+                character: 'U',
+                position: 1
+            })
+        );
+    }
+    #[test]
+    fn aliases_belong_to_the_alphabet_not_its_symbols() {
+        let plain = Alphabet::new("0123456789ABCDEFGHJKMNPQRSTVWXYZ", true);
+        assert_eq!(
+            plain.decode("O"),
+            Err(Error::InvalidCharacter {
+                character: 'O',
+                position: 0
+            })
+        );
+        let custom = Alphabet::new("01", false).with_aliases(&[('o', 0)]);
+        assert_eq!(custom.decode("1o"), Ok(vec![1, 0]));
+        assert_eq!(custom.encode(&[1, 0]), "10");
+    }
 }
@@ -1,45 +1,8 @@
-pub fn encode(plain: &str) -> String {
-    plain
-        .chars()
-        .filter(|c| c.is_ascii_alphanumeric())
-        .map(|c| {
-            if c.is_ascii_alphabetic() {
-                match c.is_uppercase() {
-                    true => char::from(b'Z' - c as u8 + b'a'),
-                    false => char::from(b'z' - c as u8 + b'a'),
-                }
-            } else {
-                c
-            }
-        })
-        .collect::<Vec<_>>()
-        .chunks(5)
-        .map(String::from_iter)
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
-/// "Decipher" with the Atbash cipher.
-pub fn decode(cipher: &str) -> String {
-    cipher
-        .chars()
-        .filter(|c| c.is_ascii_alphanumeric())
-        .map(|c| {
-            if c.is_ascii_alphabetic() {
-                (b'z' - c as u8 + b'a').into()
-            } else {
-                c
-            }
-        })
-        .collect()
-}
-
 fn main() {}
 
 #[cfg(test)]
 mod test {
-
-    use super::*;
+    use exercism_rs::cipher::atbash::*;
     #[test]
     fn encode_yes() {
         assert_eq!(encode("yes"), "bvh");
@@ -108,4 +71,8 @@ mod test {
             "anobstacleisoftenasteppingstone"
         );
     }
+    #[test]
+    fn decode_uppercase_and_punctuation() {
+        assert_eq!(decode("Svool, Dliow!"), "helloworld");
+    }
 }
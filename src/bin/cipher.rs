@@ -0,0 +1,310 @@
+use exercism_rs::cipher::Cipher;
+use exercism_rs::cipher::affine::Affine;
+use exercism_rs::cipher::analysis::{
+    Candidate, break_affine, break_rail_fence, break_rotate, break_vigenere,
+};
+use exercism_rs::cipher::atbash::Atbash;
+use exercism_rs::cipher::crypto_square::CryptoSquare;
+use exercism_rs::cipher::rail_fence::RailFence;
+use exercism_rs::cipher::rotational::Rotate;
+use exercism_rs::cipher::simple::Vigenere;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{env, fs, io, process};
+
+const USAGE: &str = "usage: cipher <CIPHER> <encode|decode|crack> [OPTIONS] [FILE]
+
+ciphers and their options:
+  rotate      --key N
+  atbash
+  affine      --a N --b N
+  vigenere    --key WORD [--pass-through]
+  railfence   --rails N [--offset N]
+  square      [--rows N] [--columns N] [--keyword WORD] [--no-padding]
+
+crack options:
+  --top N             number of ranked candidates to print (default 1)
+  --max-key-len N     longest Vigenère key to try (default 16)
+  --max-rails N       most rails to try (default 10)
+
+Reads FILE, or stdin when no FILE is given, and writes the result to stdout.";
+
+const FLAGS: [&str; 2] = ["--pass-through", "--no-padding"];
+
+struct Args {
+    cipher: String,
+    command: String,
+    options: HashMap<String, String>,
+    flags: Vec<String>,
+    path: Option<String>,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let cipher = args.next().ok_or(USAGE)?;
+        let command = args.next().ok_or(USAGE)?;
+        let mut parsed = Args {
+            cipher,
+            command,
+            options: HashMap::new(),
+            flags: vec![],
+            path: None,
+        };
+        while let Some(arg) = args.next() {
+            if FLAGS.contains(&arg.as_str()) {
+                parsed.flags.push(arg);
+            } else if arg.starts_with("--") {
+                let value = args.next().ok_or(format!("missing value for `{arg}`"))?;
+                parsed.options.insert(arg, value);
+            } else if parsed.path.is_none() {
+                parsed.path = Some(arg);
+            } else {
+                return Err(USAGE.to_string());
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn option<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.options
+            .get(name)
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| format!("invalid value `{v}` for `{name}`"))
+            })
+            .transpose()
+    }
+
+    fn required<T: FromStr>(&self, name: &str) -> Result<T, String> {
+        self.option(name)?
+            .ok_or(format!("`{}` requires `{name}`", self.cipher))
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|f| f == name)
+    }
+}
+
+fn build(args: &Args) -> Result<Box<dyn Cipher>, String> {
+    Ok(match args.cipher.as_str() {
+        "rotate" => Box::new(Rotate(args.required("--key")?)),
+        "atbash" => Box::new(Atbash),
+        "affine" => Box::new(Affine {
+            a: args.required("--a")?,
+            b: args.required("--b")?,
+        }),
+        "vigenere" => {
+            let cipher = Vigenere::new(&args.required::<String>("--key")?);
+            match args.flag("--pass-through") {
+                true => Box::new(cipher.with_pass_through()),
+                false => Box::new(cipher),
+            }
+        }
+        "railfence" => Box::new(
+            RailFence::new(args.required("--rails")?)
+                .with_offset(args.option("--offset")?.unwrap_or(0)),
+        ),
+        "square" => {
+            let mut square = CryptoSquare::new();
+            if let Some(keyword) = args.option::<String>("--keyword")? {
+                square = square.with_keyword(&keyword);
+            }
+            if let Some(rows) = args.option("--rows")? {
+                square = square.with_rows(rows);
+            }
+            if let Some(columns) = args.option("--columns")? {
+                square = square.with_columns(columns);
+            }
+            if args.flag("--no-padding") {
+                square = square.without_padding();
+            }
+            Box::new(square)
+        }
+        other => return Err(format!("unknown cipher `{other}`")),
+    })
+}
+
+fn crack(args: &Args, input: &str) -> Result<String, String> {
+    fn lines<K: Display>(candidates: Vec<Candidate<K>>) -> String {
+        candidates
+            .into_iter()
+            .map(|c| format!("{}\t{:.3}\t{}", c.key, c.score, c.plaintext))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    let top = args.option("--top")?.unwrap_or(1);
+    Ok(match args.cipher.as_str() {
+        "rotate" => lines(break_rotate(input, top)),
+        "atbash" | "affine" => lines(
+            break_affine(input, top)
+                .into_iter()
+                .map(|c| Candidate {
+                    key: format!("{},{}", c.key.0, c.key.1),
+                    plaintext: c.plaintext,
+                    score: c.score,
+                })
+                .collect(),
+        ),
+        "vigenere" => {
+            let max_len = args.option("--max-key-len")?.unwrap_or(16);
+            let broken = break_vigenere(input, max_len).ok_or("ciphertext is too short")?;
+            lines(vec![broken])
+        }
+        "railfence" => lines(
+            break_rail_fence(input, args.option("--max-rails")?.unwrap_or(10), top)
+                .into_iter()
+                .map(|c| Candidate {
+                    key: format!("{},{}", c.key.0, c.key.1),
+                    plaintext: c.plaintext,
+                    score: c.score,
+                })
+                .collect(),
+        ),
+        other => return Err(format!("`{other}` cannot be cracked")),
+    })
+}
+
+fn run(args: &Args, input: &str) -> Result<String, String> {
+    let input = input.strip_suffix('\n').unwrap_or(input);
+    match args.command.as_str() {
+        "encode" => build(args)?.encrypt(input).map_err(|e| e.to_string()),
+        "decode" => build(args)?.decrypt(input).map_err(|e| e.to_string()),
+        "crack" => crack(args, input),
+        other => Err(format!("unknown command `{other}`\n\n{USAGE}")),
+    }
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{USAGE}");
+        return;
+    }
+    let args = Args::parse(args).unwrap_or_else(|e| exit_with(&e));
+
+    let input = match &args.path {
+        Some(path) => fs::read_to_string(path),
+        None => io::read_to_string(io::stdin()),
+    }
+    .unwrap_or_else(|e| exit_with(&e.to_string()));
+
+    match run(&args, &input) {
+        Ok(output) => println!("{output}"),
+        Err(e) => exit_with(&e),
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    fn run_with(args: &str, input: &str) -> Result<String, String> {
+        let args = Args::parse(args.split_whitespace().map(String::from))?;
+        run(&args, input)
+    }
+    #[test]
+    fn rotate_encode_and_decode() {
+        assert_eq!(
+            run_with("rotate encode --key 13", "Hello\n"),
+            Ok("Uryyb".to_string())
+        );
+        assert_eq!(
+            run_with("rotate decode --key 13", "Uryyb"),
+            Ok("Hello".to_string())
+        );
+    }
+    #[test]
+    fn affine_reports_not_coprime() {
+        assert_eq!(
+            run_with("affine encode --a 6 --b 1", "hello"),
+            Err("key `6` is not coprime with the alphabet size".to_string())
+        );
+    }
+    #[test]
+    fn missing_key_is_an_error() {
+        assert_eq!(
+            run_with("vigenere encode", "hello"),
+            Err("`vigenere` requires `--key`".to_string())
+        );
+        assert_eq!(
+            run_with("rotate encode --key x", "hello"),
+            Err("invalid value `x` for `--key`".to_string())
+        );
+    }
+    #[test]
+    fn vigenere_pass_through_flag() {
+        assert_eq!(
+            run_with("vigenere encode --key b --pass-through", "Hi there"),
+            Ok("Ij uifsf".to_string())
+        );
+    }
+    #[test]
+    fn railfence_with_offset() {
+        let encoded = run_with("railfence encode --rails 3 --offset 2", "ERARAWENO");
+        assert_eq!(encoded, Ok("AERRWNEAO".to_string()));
+    }
+    #[test]
+    fn square_with_keyword() {
+        let args = "square encode --keyword zebras --no-padding";
+        assert_eq!(
+            run_with(args, "We are discovered, flee at once"),
+            Ok("evln acdt esea rofo deec wiree".to_string())
+        );
+    }
+    #[test]
+    fn crack_rotate() {
+        let output = run_with(
+            "rotate crack",
+            "Gur dhvpx oebja sbk whzcf bire gur ynml qbt",
+        )
+        .unwrap();
+        assert!(output.starts_with("13\t"));
+        assert!(output.ends_with("The quick brown fox jumps over the lazy dog"));
+    }
+    #[test]
+    fn square_cannot_be_cracked() {
+        assert_eq!(
+            run_with("square crack", "abc"),
+            Err("`square` cannot be cracked".to_string())
+        );
+    }
+    #[test]
+    fn unknown_cipher() {
+        assert_eq!(
+            run_with("enigma encode", "abc"),
+            Err("unknown cipher `enigma`".to_string())
+        );
+    }
+    #[test]
+    fn uppercase_and_punctuated_input() {
+        assert_eq!(
+            run_with("atbash decode", "Svool!\n"),
+            Ok("hello".to_string())
+        );
+        assert_eq!(
+            run_with("affine encode --a 5 --b 7", "Hello, World!\n"),
+            Ok("qbkkz nzokw".to_string())
+        );
+        assert_eq!(
+            run_with("affine decode --a 5 --b 7", "QBKKZ NZOKW\n"),
+            Ok("helloworld".to_string())
+        );
+    }
+    #[test]
+    fn crack_punctuated_input() {
+        let ciphertext = "Vg jnf gur orfg bs gvzrf, vg jnf gur jbefg bs gvzrf!\n";
+        let output = run_with("affine crack", ciphertext).unwrap();
+        assert!(output.starts_with("1,13\t"));
+        let ciphertext = "Rg dzh gsv yvhg lu grnvh, rg dzh gsv dlihg lu grnvh!\n";
+        let output = run_with("atbash crack", ciphertext).unwrap();
+        assert!(output.starts_with("25,25\t"));
+        assert!(output.ends_with("itwasthebestoftimesitwastheworstoftimes"));
+    }
+}
@@ -1,28 +1,5 @@
-pub fn encrypt(input: &str) -> String {
-    let plain: String = input
-        .chars()
-        .filter(|&c| c.is_ascii_alphanumeric())
-        .map(|c| c.to_ascii_lowercase())
-        .collect();
+use exercism_rs::cipher::crypto_square::*;
 
-    let len = plain.len();
-    if len == 0 {
-        return "".into();
-    }
-
-    let c = (len as f64).sqrt().ceil() as usize;
-
-    plain
-        .chars()
-        .collect::<Vec<_>>()
-        .chunks_mut(c)
-        .fold(vec![String::new(); c], |mut res, curr| {
-            (0..c).for_each(|row| res[row].push(*curr.get(row).unwrap_or(&' ')));
-
-            res
-        })
-        .join(" ")
-}
 fn main() {
     println!(
         "{:?}",
@@ -82,4 +59,84 @@ mod test {
         let expected = "imtgdvs fearwer mayoogo anouuio ntnnlvt wttddes aohghn  sseoau ";
         assert_eq!(&actual, expected);
     }
+    const PLAIN_TEXT: &str =
+        "If man was meant to stay on the ground, god would have given us roots.";
+    const NORMALIZED: &str = "ifmanwasmeanttostayonthegroundgodwouldhavegivenusroots";
+    #[test]
+    fn decrypt_restores_normalized_plaintext() {
+        let actual = decrypt("imtgdvs fearwer mayoogo anouuio ntnnlvt wttddes aohghn  sseoau ");
+        assert_eq!(actual.as_deref(), Some(NORMALIZED));
+    }
+    #[test]
+    fn decrypt_short_texts() {
+        assert_eq!(decrypt("").as_deref(), Some(""));
+        assert_eq!(decrypt("a").as_deref(), Some("a"));
+        assert_eq!(decrypt("clu hlt io ").as_deref(), Some("chillout"));
+    }
+    #[test]
+    fn decrypt_rejects_malformed_ciphertext() {
+        assert_eq!(decrypt("clu hlt io"), None);
+    }
+    #[test]
+    fn explicit_columns() {
+        let square = CryptoSquare::new().with_columns(3);
+        assert_eq!(square.encrypt("Chill out."), Ok("clu hlt io ".to_string()));
+        let square = CryptoSquare::new().with_columns(2);
+        assert_eq!(square.encrypt("Chill out."), Ok("cilu hlot".to_string()));
+        assert_eq!(square.decrypt("cilu hlot"), Ok("chillout".to_string()));
+    }
+    #[test]
+    fn explicit_rows_and_columns() {
+        let square = CryptoSquare::new().with_rows(2).with_columns(5);
+        assert_eq!(
+            square.encrypt("Chill out."),
+            Ok("co hu it l  l ".to_string())
+        );
+        assert_eq!(square.decrypt("co hu it l  l "), Ok("chillout".to_string()));
+    }
+    #[test]
+    fn rectangle_too_small() {
+        let square = CryptoSquare::new().with_rows(2).with_columns(3);
+        assert_eq!(
+            square.encrypt("Chill out."),
+            Err(CryptoSquareError::TooSmall {
+                rows: 2,
+                columns: 3,
+                len: 8
+            })
+        );
+        let square = CryptoSquare::new().with_rows(0);
+        assert_eq!(square.encrypt("a"), Err(CryptoSquareError::ZeroDimension));
+    }
+    #[test]
+    fn drop_padding() {
+        let square = CryptoSquare::new().without_padding();
+        let encrypted = square.encrypt(PLAIN_TEXT).unwrap();
+        assert_eq!(
+            encrypted,
+            "imtgdvs fearwer mayoogo anouuio ntnnlvt wttddes aohghn sseoau"
+        );
+        assert_eq!(square.decrypt(&encrypted).as_deref(), Ok(NORMALIZED));
+    }
+    #[test]
+    fn column_order() {
+        let square = CryptoSquare::new().with_column_order(vec![2, 0, 1]);
+        assert_eq!(square.encrypt("Chill out."), Ok("io  clu hlt".to_string()));
+        assert_eq!(square.decrypt("io  clu hlt"), Ok("chillout".to_string()));
+    }
+    #[test]
+    fn keyword_transposition() {
+        let square = CryptoSquare::new().with_keyword("zebras").without_padding();
+        let encrypted = square.encrypt("We are discovered, flee at once").unwrap();
+        assert_eq!(encrypted, "evln acdt esea rofo deec wiree");
+        assert_eq!(
+            square.decrypt(&encrypted),
+            Ok("wearediscoveredfleeatonce".to_string())
+        );
+    }
+    #[test]
+    fn invalid_column_order() {
+        let square = CryptoSquare::new().with_column_order(vec![0, 0, 1]);
+        assert_eq!(square.encrypt("abc"), Err(CryptoSquareError::InvalidOrder));
+    }
 }
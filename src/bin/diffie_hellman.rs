@@ -1,26 +1,91 @@
+use exercism_rs::number_theory::modular::pow_mod;
+use exercism_rs::number_theory::primality::is_prime;
+use rand::rngs::OsRng;
+use rand::{Rng, TryRngCore};
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyError {
+    /// The key is not an element of the group, i.e. not in `1..p`.
+    OutOfRange(u64),
+    /// `1` and `p - 1` generate subgroups of order 1 and 2, so the shared
+    /// secret would be guessable.
+    SmallSubgroup(u64),
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyError::OutOfRange(key) => write!(f, "public key `{key}` is out of range"),
+            KeyError::SmallSubgroup(key) => {
+                write!(f, "public key `{key}` lies in a small subgroup")
+            }
+        }
+    }
+}
+
+/// A private key drawn from the operating system's CSPRNG.
 pub fn private_key(p: u64) -> u64 {
-    (p + 1) / 2
+    private_key_with(&mut OsRng.unwrap_err(), p)
+}
+
+/// A private key in `2..p`, drawn from `rng`.
+///
+/// # Panics
+///
+/// Panics if `p < 3`, since `2..p` is then empty.
+pub fn private_key_with<R: Rng + ?Sized>(rng: &mut R, p: u64) -> u64 {
+    assert!(p >= 3, "modulus `{p}` is too small");
+    rng.random_range(2..p)
 }
 
 pub fn public_key(p: u64, g: u64, a: u64) -> u64 {
-    mod_power(p, g, a)
+    pow_mod(g, a, p)
 }
 
 pub fn secret(p: u64, b_pub: u64, a: u64) -> u64 {
-    mod_power(p, b_pub, a)
+    pow_mod(b_pub, a, p)
+}
+
+/// Like [`secret`], but first rejects public keys that would leak the secret.
+pub fn try_secret(p: u64, b_pub: u64, a: u64) -> Result<u64, KeyError> {
+    validate_public_key(p, b_pub)?;
+    Ok(secret(p, b_pub, a))
+}
+
+pub fn validate_public_key(p: u64, key: u64) -> Result<(), KeyError> {
+    match key {
+        0 => Err(KeyError::OutOfRange(key)),
+        key if key >= p => Err(KeyError::OutOfRange(key)),
+        key if key == 1 || key == p - 1 => Err(KeyError::SmallSubgroup(key)),
+        _ => Ok(()),
+    }
+}
+
+/// Whether `p = 2q + 1` with both `p` and `q` prime.
+pub fn is_safe_prime(p: u64) -> bool {
+    p > 4 && is_prime(p) && is_prime(p / 2)
 }
 
-fn mod_power(p: u64, num: u64, mut exp: u64) -> u64 {
-    let mut power: u64 = num % p;
-    let mut acc: u64 = 1;
-    while exp != 0 {
-        if exp & 1 == 1 {
-            acc = (acc * power) % p;
+/// A random safe prime of exactly `bits` bits.
+pub fn safe_prime<R: Rng + ?Sized>(rng: &mut R, bits: u32) -> u64 {
+    assert!((3..=64).contains(&bits), "`{bits}` bits is not supported");
+    let low = 1 << (bits - 2);
+    loop {
+        let q = rng.random_range(low..low * 2) | 1;
+        if is_prime(q) && is_prime(2 * q + 1) {
+            return 2 * q + 1;
         }
-        exp /= 2;
-        power = (power * power) % p;
     }
-    acc
+}
+
+/// A generator of the subgroup of quadratic residues modulo the safe prime
+/// `p`. That subgroup has prime order `(p - 1) / 2`, so every element other
+/// than 1 generates it.
+pub fn generator<R: Rng + ?Sized>(rng: &mut R, p: u64) -> u64 {
+    assert!(is_safe_prime(p), "`{p}` is not a safe prime");
+    let h = rng.random_range(2..p - 1);
+    pow_mod(h, 2, p)
 }
 
 fn main() {}
@@ -28,6 +93,8 @@ fn main() {}
 #[cfg(test)]
 mod test {
     use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
     #[test]
     fn private_key_in_range_key() {
         let primes: Vec<u64> = vec![
@@ -73,16 +140,11 @@ mod test {
         assert_eq!(secret, expected);
     }
     // two biggest 64bit primes
-    #[cfg(feature = "big-primes")]
     const PRIME_64BIT_1: u64 = 0xFFFF_FFFF_FFFF_FFC5;
-    #[cfg(feature = "big-primes")]
     const PRIME_64BIT_2: u64 = 0xFFFF_FFFF_FFFF_FFAC;
-    #[cfg(feature = "big-primes")]
     const PRIVATE_KEY_64BIT: u64 = 0xFFFF_FFFF_FFFF_FFC3;
-    #[cfg(feature = "big-primes")]
     const PUBLIC_KEY_64BIT: u64 = 0xB851_EB85_1EB8_51C1;
     #[test]
-    #[cfg(feature = "big-primes")]
     fn public_key_correct_biggest_numbers() {
         assert_eq!(
             public_key(PRIME_64BIT_1, PRIME_64BIT_2, PRIVATE_KEY_64BIT),
@@ -90,7 +152,6 @@ mod test {
         );
     }
     #[test]
-    #[cfg(feature = "big-primes")]
     fn secret_key_correct_biggest_numbers() {
         let private_key_b = 0xEFFF_FFFF_FFFF_FFC0;
         let public_key_b = public_key(PRIME_64BIT_1, PRIME_64BIT_2, private_key_b);
@@ -103,7 +164,6 @@ mod test {
         assert_eq!(secret_key, expected_key);
     }
     #[test]
-    #[cfg(feature = "big-primes")]
     fn changed_secret_key_biggest_numbers() {
         let private_key_a = private_key(PRIME_64BIT_1);
         let public_key_a = public_key(PRIME_64BIT_1, PRIME_64BIT_2, private_key_a);
@@ -126,4 +186,55 @@ mod test {
         let secret_b = secret(p, public_key_a, private_key_b);
         assert_eq!(secret_a, secret_b);
     }
+    #[test]
+    fn seeded_private_keys_are_reproducible() {
+        let a = private_key_with(&mut StdRng::seed_from_u64(7), PRIME_64BIT_1);
+        let b = private_key_with(&mut StdRng::seed_from_u64(7), PRIME_64BIT_1);
+        assert_eq!(a, b);
+        assert!(1 < a && a < PRIME_64BIT_1);
+    }
+    #[test]
+    fn smallest_modulus_has_a_single_private_key() {
+        assert_eq!(private_key(3), 2);
+    }
+    #[test]
+    fn invalid_public_keys_are_rejected() {
+        let p = 23;
+        assert_eq!(validate_public_key(p, 0), Err(KeyError::OutOfRange(0)));
+        assert_eq!(validate_public_key(p, 23), Err(KeyError::OutOfRange(23)));
+        assert_eq!(validate_public_key(p, 1), Err(KeyError::SmallSubgroup(1)));
+        assert_eq!(validate_public_key(p, 22), Err(KeyError::SmallSubgroup(22)));
+        assert_eq!(validate_public_key(p, 8), Ok(()));
+        assert_eq!(try_secret(p, 22, 6), Err(KeyError::SmallSubgroup(22)));
+        assert_eq!(try_secret(p, 19, 6), Ok(secret(p, 19, 6)));
+    }
+    #[test]
+    fn safe_primes_have_the_requested_size() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for bits in [3, 8, 32, 62, 64] {
+            let p = safe_prime(&mut rng, bits);
+            assert!(is_safe_prime(p));
+            assert_eq!(64 - p.leading_zeros(), bits);
+        }
+        assert!(is_safe_prime(23));
+        assert!(!is_safe_prime(13));
+    }
+    #[test]
+    fn generator_has_prime_order() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let p = safe_prime(&mut rng, 64);
+        let g = generator(&mut rng, p);
+        assert_ne!(g, 1);
+        assert_eq!(pow_mod(g, (p - 1) / 2, p), 1);
+    }
+    #[test]
+    fn key_exchange_over_generated_group() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let p = safe_prime(&mut rng, 63);
+        let g = generator(&mut rng, p);
+        let a = private_key_with(&mut rng, p);
+        let b = private_key_with(&mut rng, p);
+        let (a_pub, b_pub) = (public_key(p, g, a), public_key(p, g, b));
+        assert_eq!(try_secret(p, b_pub, a), try_secret(p, a_pub, b));
+    }
 }
@@ -1,11 +1,7 @@
-fn is_prime(n: u32) -> bool {
-    if n < 2 {
-        return false;
-    }
-    (2..=(n / 2)).all(|i| n % i != 0)
-}
+use exercism_rs::number_theory::sieve;
+
 pub fn nth(n: u32) -> u32 {
-    (2_u32..).filter(|&s| is_prime(s)).nth(n as usize).unwrap()
+    sieve::nth_prime(n as u64) as u32
 }
 
 fn main() {}
@@ -37,4 +33,10 @@ mod test {
         let expected = 104_743;
         assert_eq!(output, expected);
     }
+    #[test]
+    fn millionth_prime() {
+        let output = nth(999_999);
+        let expected = 15_485_863;
+        assert_eq!(output, expected);
+    }
 }
@@ -1,4 +1,7 @@
+use exercism_rs::number_theory::divisors::{aliquot_sum, aliquot_sums};
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Classification {
@@ -7,21 +10,107 @@ pub enum Classification {
     Deficient,
 }
 
+impl Classification {
+    fn of(num: u64, aliquot_sum: u128) -> Self {
+        match (num as u128).cmp(&aliquot_sum) {
+            Ordering::Equal => Classification::Perfect,
+            Ordering::Greater => Classification::Deficient,
+            Ordering::Less => Classification::Abundant,
+        }
+    }
+}
+
 pub fn classify(num: u64) -> Option<Classification> {
     if num < 1 {
         return None;
     }
-    match num.cmp(&((1..=(num / 2)).filter(|n| num % n == 0).sum())) {
-        Ordering::Equal => Some(Classification::Perfect),
-        Ordering::Greater => Some(Classification::Deficient),
-        Ordering::Less => Some(Classification::Abundant),
+    Some(Classification::of(num, aliquot_sum(num)))
+}
+
+/// Classify every number in `range` at once with a divisor-sum sieve, which
+/// is far cheaper than factorising each one. Zero is skipped.
+pub fn classify_range(range: RangeInclusive<u64>) -> Vec<(u64, Classification)> {
+    aliquot_sums(range.clone())
+        .into_iter()
+        .zip(range)
+        .filter(|&(_, num)| num > 0)
+        .map(|(sum, num)| (num, Classification::of(num, sum as u128)))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fate {
+    /// The sequence reached a prime, then 1, then 0.
+    Terminates,
+    /// The sequence entered a cycle, whose first term is at `terms[start]`.
+    Cycle { kind: CycleKind, start: usize },
+    /// No verdict within the step limit, or a term no longer fits in a `u64`.
+    Unresolved,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CycleKind {
+    Perfect,
+    Amicable,
+    Sociable(usize),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AliquotSequence {
+    pub terms: Vec<u64>,
+    pub fate: Fate,
+}
+
+impl AliquotSequence {
+    /// The terms of the cycle, if the sequence entered one.
+    pub fn cycle(&self) -> Option<&[u64]> {
+        match self.fate {
+            Fate::Cycle { start, .. } => Some(&self.terms[start..]),
+            _ => None,
+        }
     }
 }
 
-fn main() {
-    let list: Vec<i32> = (1..=(24 / 2)).filter(|n| 24 % n == 0).collect();
+/// Follow `n, s(n), s(s(n)), …` for at most `max_steps` steps, stopping at 0
+/// or as soon as a term repeats.
+pub fn aliquot_sequence(n: u64, max_steps: usize) -> AliquotSequence {
+    let mut terms = vec![n];
+    let mut seen = HashMap::from([(n, 0)]);
+    let mut current = n;
+    for _ in 0..max_steps {
+        if current == 0 {
+            return AliquotSequence {
+                terms,
+                fate: Fate::Terminates,
+            };
+        }
+        let Ok(next) = u64::try_from(aliquot_sum(current)) else {
+            break;
+        };
+        if let Some(&start) = seen.get(&next) {
+            let kind = match terms.len() - start {
+                1 => CycleKind::Perfect,
+                2 => CycleKind::Amicable,
+                len => CycleKind::Sociable(len),
+            };
+            return AliquotSequence {
+                terms,
+                fate: Fate::Cycle { kind, start },
+            };
+        }
+        seen.insert(next, terms.len());
+        terms.push(next);
+        current = next;
+    }
+    let fate = match current {
+        0 => Fate::Terminates,
+        _ => Fate::Unresolved,
+    };
+    AliquotSequence { terms, fate }
 }
 
+fn main() {}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -108,4 +197,76 @@ mod test {
         let output = classify(input);
         assert!(output.is_none());
     }
+    #[test]
+    fn classify_range_matches_classify() {
+        let range = classify_range(0..=10_000);
+        assert_eq!(range.len(), 10_000);
+        for (num, classification) in range {
+            assert_eq!(classify(num), Some(classification), "{num}");
+        }
+    }
+    #[test]
+    fn perfect_numbers_below_ten_thousand() {
+        let perfect = classify_range(1..=10_000)
+            .into_iter()
+            .filter(|(_, c)| *c == Classification::Perfect)
+            .map(|(num, _)| num)
+            .collect::<Vec<_>>();
+        assert_eq!(perfect, [6, 28, 496, 8128]);
+    }
+    #[test]
+    fn aliquot_sequence_terminates() {
+        let sequence = aliquot_sequence(12, 100);
+        assert_eq!(sequence.terms, [12, 16, 15, 9, 4, 3, 1, 0]);
+        assert_eq!(sequence.fate, Fate::Terminates);
+        assert_eq!(sequence.cycle(), None);
+    }
+    #[test]
+    fn aliquot_sequence_perfect() {
+        let sequence = aliquot_sequence(28, 100);
+        assert_eq!(sequence.terms, [28]);
+        assert_eq!(
+            sequence.fate,
+            Fate::Cycle {
+                kind: CycleKind::Perfect,
+                start: 0
+            }
+        );
+        // 25 is "aspiring": it is not perfect but reaches a perfect number.
+        let sequence = aliquot_sequence(25, 100);
+        assert_eq!(sequence.terms, [25, 6]);
+        assert_eq!(sequence.cycle(), Some(&[6][..]));
+    }
+    #[test]
+    fn aliquot_sequence_amicable() {
+        let sequence = aliquot_sequence(220, 100);
+        assert_eq!(sequence.terms, [220, 284]);
+        assert_eq!(
+            sequence.fate,
+            Fate::Cycle {
+                kind: CycleKind::Amicable,
+                start: 0
+            }
+        );
+    }
+    #[test]
+    fn aliquot_sequence_sociable() {
+        let sequence = aliquot_sequence(12_496, 100);
+        assert_eq!(sequence.terms, [12_496, 14_288, 15_472, 14_536, 14_264]);
+        assert_eq!(
+            sequence.fate,
+            Fate::Cycle {
+                kind: CycleKind::Sociable(5),
+                start: 0
+            }
+        );
+    }
+    #[test]
+    fn aliquot_sequence_gives_up_at_the_step_limit() {
+        // 276 is the smallest number whose sequence is still open.
+        let sequence = aliquot_sequence(276, 50);
+        assert_eq!(sequence.terms.len(), 51);
+        assert_eq!(sequence.fate, Fate::Unresolved);
+        assert_eq!(aliquot_sequence(12, 0).fate, Fate::Unresolved);
+    }
 }
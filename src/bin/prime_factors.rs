@@ -1,18 +1,7 @@
-pub fn factors(mut n: u64) -> Vec<u64> {
-    let mut output = vec![];
+use exercism_rs::number_theory::factor;
 
-    let mut prime = 2;
-
-    while n > 1 {
-        if n % prime == 0 {
-            output.push(prime);
-            n /= prime;
-        } else {
-            prime += 1;
-        }
-    }
-
-    output
+pub fn factors(n: u64) -> Vec<u64> {
+    factor::factors(n)
 }
 
 fn main() {}
@@ -92,4 +81,10 @@ mod test {
         let expected = [11, 9_539, 894_119];
         assert_eq!(factors, expected);
     }
+    #[test]
+    fn product_of_two_large_primes() {
+        let factors = factors(18_446_743_979_220_271_189);
+        let expected = [4_294_967_279, 4_294_967_291];
+        assert_eq!(factors, expected);
+    }
 }
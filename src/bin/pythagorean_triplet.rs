@@ -1,22 +1,124 @@
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+/// What bounds the triplets, and therefore the order they come out in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// `a + b + c <= n`, yielded by increasing perimeter.
+    Perimeter(u64),
+    /// `c <= n`, yielded by increasing hypotenuse.
+    Hypotenuse(u64),
+}
+
+impl Bound {
+    fn key(&self, [a, b, c]: [u64; 3]) -> u64 {
+        match self {
+            Bound::Perimeter(_) => a + b + c,
+            Bound::Hypotenuse(_) => c,
+        }
+    }
+
+    fn limit(&self) -> u64 {
+        match self {
+            Bound::Perimeter(n) | Bound::Hypotenuse(n) => *n,
+        }
+    }
+}
+
+/// A pending triplet: its sort key and sides, Euclid's `m` and `n`, and the
+/// multiple `k` of the primitive triplet they generate.
+type Entry = Reverse<(u64, [u64; 3], u64, u64, u64)>;
+
+/// Pythagorean triplets `[a, b, c]` with `a < b < c`, generated by Euclid's
+/// formula `(m² - n², 2mn, m² + n²)` for coprime `m > n` of opposite parity.
+/// Each `m` yields keys increasing in `n`, and the smallest key for `m` grows
+/// with `m`, so a heap merging one frontier entry per `m` yields triplets in
+/// order. Ties are broken by the sides.
+#[derive(Debug, Clone)]
+pub struct Triplets {
+    bound: Bound,
+    multiples: bool,
+    heap: BinaryHeap<Entry>,
+    /// The smallest `m` with no entry pushed yet.
+    next_m: u64,
+}
+
+pub fn triplets(bound: Bound) -> Triplets {
+    let mut triplets = Triplets {
+        bound,
+        multiples: false,
+        heap: BinaryHeap::new(),
+        next_m: 2,
+    };
+    triplets.seed_next_m();
+    triplets
+}
+
+impl Triplets {
+    /// Also yield non-primitive triplets such as `[6, 8, 10]`.
+    pub fn with_multiples(mut self) -> Self {
+        self.multiples = true;
+        self
+    }
+
+    fn entry(&self, m: u64, n: u64, k: u64) -> Option<Entry> {
+        let (x, y) = (m * m - n * n, 2 * m * n);
+        let sides = [k * x.min(y), k * x.max(y), k * (m * m + n * n)];
+        let key = self.bound.key(sides);
+        (key <= self.bound.limit()).then_some(Reverse((key, sides, m, n, k)))
+    }
+
+    fn seed_next_m(&mut self) {
+        let m = self.next_m;
+        self.next_m += 1;
+        if let Some(entry) = self.entry(m, 1 + m % 2, 1) {
+            self.heap.push(entry);
+        }
+    }
+}
+
+impl Iterator for Triplets {
+    type Item = [u64; 3];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Reverse((_, sides, m, n, k)) = self.heap.pop()?;
+            if m + 1 == self.next_m && n <= 2 && k == 1 {
+                self.seed_next_m();
+            }
+            if k == 1 && n + 2 < m {
+                self.heap.extend(self.entry(m, n + 2, 1));
+            }
+            if gcd(m, n) != 1 {
+                continue;
+            }
+            if self.multiples {
+                self.heap.extend(self.entry(m, n, k + 1));
+            }
+            return Some(sides);
+        }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
 
 pub fn find(sum: u32) -> HashSet<[u32; 3]> {
-    (1..sum / 3)
-        .flat_map(|a| {
-            ((a + 1)..=sum / 2)
-                .map(move |b| {
-                    let c = sum - a - b;
-                    (a * a + b * b == c * c).then_some([a, b, c])
-                })
-                .filter(Option::is_some)
+    let sum = sum as u64;
+    triplets(Bound::Perimeter(sum))
+        .filter(|[a, b, c]| sum.is_multiple_of(a + b + c))
+        .map(|[a, b, c]| {
+            let k = sum / (a + b + c);
+            [(k * a) as u32, (k * b) as u32, (k * c) as u32]
         })
-        .map(|v| v.unwrap())
         .collect()
 }
 
-fn main() {
-    println!("{}", 12f64.sqrt())
-}
+fn main() {}
 
 #[cfg(test)]
 mod test {
@@ -93,4 +195,74 @@ mod test {
         let expected: HashSet<_> = expected.iter().cloned().collect();
         assert_eq!(output, expected);
     }
+    #[test]
+    fn primitive_triplets_by_perimeter() {
+        let output = triplets(Bound::Perimeter(90)).collect::<Vec<_>>();
+        let expected = [
+            [3, 4, 5],
+            [5, 12, 13],
+            [8, 15, 17],
+            [7, 24, 25],
+            [20, 21, 29],
+            [12, 35, 37],
+            [9, 40, 41],
+        ];
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn primitive_triplets_by_hypotenuse() {
+        let output = triplets(Bound::Hypotenuse(41)).collect::<Vec<_>>();
+        let expected = [
+            [3, 4, 5],
+            [5, 12, 13],
+            [8, 15, 17],
+            [7, 24, 25],
+            [20, 21, 29],
+            [12, 35, 37],
+            [9, 40, 41],
+        ];
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn triplets_with_multiples_by_hypotenuse() {
+        let output = triplets(Bound::Hypotenuse(20))
+            .with_multiples()
+            .collect::<Vec<_>>();
+        let expected = [
+            [3, 4, 5],
+            [6, 8, 10],
+            [5, 12, 13],
+            [9, 12, 15],
+            [8, 15, 17],
+            [12, 16, 20],
+        ];
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn multiples_match_brute_force() {
+        let limit = 500;
+        let mut expected = vec![];
+        for c in 1..=limit {
+            for a in 1..c {
+                for b in a + 1..c {
+                    if a * a + b * b == c * c && a + b + c <= limit {
+                        expected.push((a + b + c, [a, b, c]));
+                    }
+                }
+            }
+        }
+        expected.sort();
+        let expected = expected.into_iter().map(|(_, t)| t).collect::<Vec<_>>();
+        let output = triplets(Bound::Perimeter(limit))
+            .with_multiples()
+            .collect::<Vec<_>>();
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn triplets_for_a_sum_in_the_millions() {
+        let output = find(12_000_000);
+        assert!(output.contains(&[3_000_000, 4_000_000, 5_000_000]));
+        assert!(output.iter().all(|&[a, b, c]| a + b + c == 12_000_000
+            && (a as u64).pow(2) + (b as u64).pow(2) == (c as u64).pow(2)));
+    }
 }
@@ -1,41 +1,7 @@
-pub struct RailFence(usize);
+use exercism_rs::cipher::rail_fence::*;
 
-impl RailFence {
-    pub fn new(rails: u32) -> RailFence {
-        RailFence(rails as usize)
-    }
-
-    pub fn encode(&self, text: &str) -> String {
-        text.chars()
-            .zip(indexes(self.0))
-            .fold(vec![String::new(); self.0], |mut res, (c, idx)| {
-                res[idx].push(c);
-                res
-            })
-            .join("")
-    }
-
-    pub fn decode(&self, cipher: &str) -> String {
-        let mut row_indexes: Vec<_> = indexes(self.0).zip(0..).take(cipher.len()).collect();
-        row_indexes.sort();
-        let mut char_with_index: Vec<_> = cipher
-            .chars()
-            .zip(row_indexes)
-            .map(|(c, (_, i))| (i, c))
-            .collect();
-        char_with_index.sort();
-        char_with_index.into_iter().map(|(_, c)| c).collect()
-    }
-}
-
-fn indexes(n: usize) -> impl Iterator<Item = usize> {
-    (0..n).chain((1..n - 1).rev()).cycle()
-}
 fn main() {
-    let mut indexesdd: Vec<_> = indexes(4).zip(1..).take(10).collect();
-    indexesdd.sort();
-
-    println!("{:?}", indexesdd);
+    println!("{}", RailFence::new(3).render("WEAREDISCOVEREDFLEEATONCE"));
 }
 
 #[cfg(test)]
@@ -118,4 +84,60 @@ mod test {
         let expected = "古びの池飛込水音蛙む";
         assert_eq!(output, expected);
     }
+    #[test]
+    fn one_rail_is_the_identity() {
+        let rail_fence = RailFence::new(1);
+        assert_eq!(rail_fence.encode("ONE RAIL"), "ONE RAIL");
+        assert_eq!(rail_fence.decode("ONE RAIL"), "ONE RAIL");
+    }
+    #[test]
+    fn zero_rails_do_not_panic() {
+        assert_eq!(RailFence::new(0).encode("ABC"), "ABC");
+    }
+    #[test]
+    fn encode_with_offset() {
+        // ..A...E..
+        // .R.R.W.N.
+        // E...A...O
+        let rail_fence = RailFence::new(3).with_offset(2);
+        assert_eq!(rail_fence.encode("ERARAWENO"), "AERRWNEAO");
+        assert_eq!(rail_fence.decode("AERRWNEAO"), "ERARAWENO");
+    }
+    #[test]
+    fn offset_wraps_around_the_cycle() {
+        let input = "WEAREDISCOVEREDFLEEATONCE";
+        assert_eq!(
+            RailFence::new(3).with_offset(5).encode(input),
+            RailFence::new(3).with_offset(1).encode(input)
+        );
+    }
+    #[test]
+    fn offsets_round_trip() {
+        let input = "THEDEVILISINTHEDETAILS";
+        for offset in 0..8 {
+            let rail_fence = RailFence::new(5).with_offset(offset);
+            assert_eq!(rail_fence.decode(&rail_fence.encode(input)), input);
+        }
+    }
+    #[test]
+    fn render_zigzag() {
+        let expected = [
+            "W...E...C...R...L...T...E",
+            ".E.R.D.S.O.E.E.F.E.A.O.C.",
+            "..A...I...V...D...E...N..",
+        ]
+        .join("\n");
+        assert_eq!(
+            RailFence::new(3).render("WEAREDISCOVEREDFLEEATONCE"),
+            expected
+        );
+    }
+    #[test]
+    fn render_with_offset() {
+        let expected = ["..A...E..", ".R.R.W.N.", "E...A...O"].join("\n");
+        assert_eq!(
+            RailFence::new(3).with_offset(2).render("ERARAWENO"),
+            expected
+        );
+    }
 }
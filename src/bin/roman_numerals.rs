@@ -1,82 +1,437 @@
-use std::fmt::{Display, Formatter, Result};
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
 
-pub struct Roman(String);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Roman(u32);
+
+/// The largest value the vinculum can express, `M̅M̅M̅C̅M̅X̅C̅I̅X̅CMXCIX`.
+pub const MAX: u32 = 3_999_999;
+
+/// How the vinculum (overline, multiplying by 1000) is written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Style {
+    /// A combining overline (U+0305) after each symbol: `I̅V̅`.
+    #[default]
+    Unicode,
+    /// An underscore before each symbol: `_I_V`.
+    Ascii,
+}
+
+impl Roman {
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    /// Values of 4000 and above put the thousands under a vinculum.
+    pub fn format(&self, style: Style) -> String {
+        if self.0 < 4000 {
+            return numeral(self.0);
+        }
+        let high = numeral(self.0 / 1000)
+            .chars()
+            .map(|c| match style {
+                Style::Unicode => format!("{c}\u{305}"),
+                Style::Ascii => format!("_{c}"),
+            })
+            .collect::<String>();
+        high + &numeral(self.0 % 1000)
+    }
+
+    /// `None` if the sum exceeds [`MAX`].
+    pub fn checked_add(self, rhs: Roman) -> Option<Roman> {
+        Roman::try_from(self.0 + rhs.0).ok()
+    }
+
+    /// `None` unless `rhs` is smaller than `self`.
+    pub fn checked_sub(self, rhs: Roman) -> Option<Roman> {
+        self.0
+            .checked_sub(rhs.0)
+            .and_then(|n| Roman::try_from(n).ok())
+    }
+
+    pub fn parse_with(s: &str, mode: Mode) -> Result<Roman, RomanError> {
+        match mode {
+            Mode::Strict => parse_strict(s),
+            Mode::Lenient => parse_lenient(s),
+        }
+        .map(Roman)
+    }
+}
 
 impl Display for Roman {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "{}", self.0)
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(Style::Unicode))
     }
 }
 
-impl From<u32> for Roman {
-    fn from(mut num: u32) -> Self {
-        let mut inner = String::new();
-        // M	  D	  C	  L	  X	  V	I
-        // 1000	500	100	50	10	5	1
-        while num > 0 {
-            match num {
-                1000.. => {
-                    let n = (num / 1000) as usize;
-                    num %= 1000;
-                    inner.push_str("M".repeat(n).as_str());
-                }
-                900.. => {
-                    num %= 900;
-                    inner.push_str("CM");
-                }
-                500.. => {
-                    num %= 500;
-                    inner.push('D');
-                }
-                400.. => {
-                    num %= 400;
-                    inner.push_str("CD");
-                }
-                100.. => {
-                    let n = (num / 100) as usize;
-                    num %= 100;
-                    inner.push_str("C".repeat(n).as_str());
-                }
-                90.. => {
-                    num %= 90;
-                    inner.push_str("XC");
-                }
-                50.. => {
-                    num %= 50;
-                    inner.push('L');
-                }
-                40.. => {
-                    num %= 40;
-                    inner.push_str("XL");
-                }
-                10.. => {
-                    let n = (num / 10) as usize;
-                    num %= 10;
-                    inner.push_str("X".repeat(n).as_str());
-                }
-                9 => {
-                    num %= 9;
-                    inner.push_str("IX");
-                }
-                5.. => {
-                    num %= 5;
-                    inner.push('V');
-                }
-                4 => {
-                    num %= 4;
-                    inner.push_str("IV");
+impl TryFrom<u32> for Roman {
+    type Error = RomanError;
+
+    fn try_from(num: u32) -> Result<Self, Self::Error> {
+        match num {
+            0 => Err(RomanError::Zero),
+            1..=MAX => Ok(Self(num)),
+            _ => Err(RomanError::OutOfRange(num)),
+        }
+    }
+}
+
+fn numeral(mut num: u32) -> String {
+    let mut inner = String::new();
+    // M	  D	  C	  L	  X	  V	I
+    // 1000	500	100	50	10	5	1
+    while num > 0 {
+        match num {
+            1000.. => {
+                let n = (num / 1000) as usize;
+                num %= 1000;
+                inner.push_str("M".repeat(n).as_str());
+            }
+            900.. => {
+                num %= 900;
+                inner.push_str("CM");
+            }
+            500.. => {
+                num %= 500;
+                inner.push('D');
+            }
+            400.. => {
+                num %= 400;
+                inner.push_str("CD");
+            }
+            100.. => {
+                let n = (num / 100) as usize;
+                num %= 100;
+                inner.push_str("C".repeat(n).as_str());
+            }
+            90.. => {
+                num %= 90;
+                inner.push_str("XC");
+            }
+            50.. => {
+                num %= 50;
+                inner.push('L');
+            }
+            40.. => {
+                num %= 40;
+                inner.push_str("XL");
+            }
+            10.. => {
+                let n = (num / 10) as usize;
+                num %= 10;
+                inner.push_str("X".repeat(n).as_str());
+            }
+            9 => {
+                num %= 9;
+                inner.push_str("IX");
+            }
+            5.. => {
+                num %= 5;
+                inner.push('V');
+            }
+            4 => {
+                num %= 4;
+                inner.push_str("IV");
+            }
+            1.. => {
+                inner.push_str("I".repeat(num as usize).as_str());
+                num = 0;
+            }
+            _ => panic!(),
+        }
+    }
+
+    inner
+}
+/// How forgiving parsing is about non-canonical numerals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Only the canonical form that [`Roman`] itself renders, in upper case.
+    Strict,
+    /// Also additive forms such as `IIII` or `MMMM`, and lower case. Symbols
+    /// must still not increase, so `VX` and `IC` are rejected.
+    Lenient,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RomanError {
+    Empty,
+    /// `character` at `position` (counted in characters) is not a numeral.
+    InvalidCharacter {
+        position: usize,
+        character: char,
+    },
+    /// `character` at `position` is a numeral but cannot appear there.
+    Misplaced {
+        position: usize,
+        character: char,
+    },
+    /// The Romans had no numeral for zero.
+    Zero,
+    OutOfRange(u32),
+}
+
+impl Display for RomanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RomanError::Empty => write!(f, "empty Roman numeral"),
+            RomanError::InvalidCharacter {
+                position,
+                character,
+            } => write!(
+                f,
+                "`{character}` at position {position} is not a Roman numeral"
+            ),
+            RomanError::Misplaced {
+                position,
+                character,
+            } => write!(f, "`{character}` at position {position} is out of place"),
+            RomanError::Zero => write!(f, "zero has no Roman numeral"),
+            RomanError::OutOfRange(n) => write!(f, "{n} is larger than {MAX}"),
+        }
+    }
+}
+
+impl FromStr for Roman {
+    type Err = RomanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Roman::parse_with(s, Mode::Strict)
+    }
+}
+
+impl TryFrom<&str> for Roman {
+    type Error = RomanError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+// `TryFrom<&str> for u32` would break the orphan rule; go through `Roman`.
+impl From<Roman> for u32 {
+    fn from(roman: Roman) -> Self {
+        roman.0
+    }
+}
+
+/// # Panics
+///
+/// If the sum exceeds [`MAX`]; use [`Roman::checked_add`] to avoid this.
+impl Add for Roman {
+    type Output = Roman;
+
+    fn add(self, rhs: Roman) -> Roman {
+        self.checked_add(rhs).expect("Roman numeral overflow")
+    }
+}
+
+/// # Panics
+///
+/// If `rhs` is not smaller than `self`, as there is no numeral for zero or
+/// negative values; use [`Roman::checked_sub`] to avoid this.
+impl Sub for Roman {
+    type Output = Roman;
+
+    fn sub(self, rhs: Roman) -> Roman {
+        self.checked_sub(rhs)
+            .expect("Roman numerals cannot be zero or negative")
+    }
+}
+
+fn symbol_value(c: char) -> Option<u32> {
+    match c {
+        'I' => Some(1),
+        'V' => Some(5),
+        'X' => Some(10),
+        'L' => Some(50),
+        'C' => Some(100),
+        'D' => Some(500),
+        'M' => Some(1000),
+        _ => None,
+    }
+}
+
+/// A symbol's position in the input, counted in characters, and the symbol.
+type Symbol = (usize, char);
+
+/// Split `s` into the symbols under the vinculum and those after it. `'_'`
+/// overlines the symbol after it and U+0305 the one before it.
+fn symbols(s: &str) -> Result<(Vec<Symbol>, Vec<Symbol>), RomanError> {
+    let invalid = |position, character| RomanError::InvalidCharacter {
+        position,
+        character,
+    };
+    let mut marked = Vec::<(Symbol, bool)>::new();
+    let mut underscore = None;
+    for (position, character) in s.chars().enumerate() {
+        match character {
+            '\u{305}' => match marked.last_mut() {
+                Some((_, overline @ false)) if underscore.is_none() => *overline = true,
+                _ => return Err(invalid(position, character)),
+            },
+            '_' if underscore.is_none() => underscore = Some(position),
+            _ if symbol_value(character).is_some() => {
+                marked.push(((position, character), underscore.take().is_some()));
+            }
+            _ => return Err(invalid(position, character)),
+        }
+    }
+    if let Some(position) = underscore {
+        return Err(invalid(position, '_'));
+    }
+    if marked.is_empty() {
+        return Err(RomanError::Empty);
+    }
+
+    let split = marked
+        .iter()
+        .position(|&(_, overline)| !overline)
+        .unwrap_or(marked.len());
+    if let Some(&((position, character), _)) =
+        marked[split..].iter().find(|(_, overline)| *overline)
+    {
+        return Err(RomanError::Misplaced {
+            position,
+            character,
+        });
+    }
+    let (high, low) = marked.split_at(split);
+    let strip = |part: &[(Symbol, bool)]| part.iter().map(|&(symbol, _)| symbol).collect();
+    Ok((strip(high), strip(low)))
+}
+
+/// Consume one canonical decimal place (`one`, `five`, `ten` being e.g.
+/// `X`, `L`, `C`) starting at `*pos`, returning its digit.
+fn place(symbols: &[Symbol], pos: &mut usize, one: char, five: char, ten: char) -> u32 {
+    let at = |i: usize| symbols.get(i).map(|&(_, c)| c);
+    match (at(*pos), at(*pos + 1)) {
+        (Some(a), Some(b)) if a == one && b == ten => {
+            *pos += 2;
+            return 9;
+        }
+        (Some(a), Some(b)) if a == one && b == five => {
+            *pos += 2;
+            return 4;
+        }
+        _ => {}
+    }
+    let mut digit = 0;
+    if at(*pos) == Some(five) {
+        digit = 5;
+        *pos += 1;
+    }
+    for _ in 0..3 {
+        if at(*pos) != Some(one) {
+            break;
+        }
+        digit += 1;
+        *pos += 1;
+    }
+    digit
+}
+
+/// Parse the canonical numeral below 4000, or below 1000 when
+/// `with_thousands` is off.
+fn parse_canonical(symbols: &[Symbol], with_thousands: bool) -> Result<u32, RomanError> {
+    let mut pos = 0;
+    let mut value = 0;
+    // Thousands have no five or ten symbol; NUL never matches.
+    for (unit, one, five, ten) in [
+        (1000, 'M', '\0', '\0'),
+        (100, 'C', 'D', 'M'),
+        (10, 'X', 'L', 'C'),
+        (1, 'I', 'V', 'X'),
+    ] {
+        if unit == 1000 && !with_thousands {
+            continue;
+        }
+        value += unit * place(symbols, &mut pos, one, five, ten);
+    }
+    match symbols.get(pos) {
+        Some(&(position, character)) => Err(RomanError::Misplaced {
+            position,
+            character,
+        }),
+        None => Ok(value),
+    }
+}
+
+fn parse_strict(s: &str) -> Result<u32, RomanError> {
+    let (high, low) = symbols(s)?;
+    if high.is_empty() {
+        return parse_canonical(&low, true);
+    }
+    let thousands = parse_canonical(&high, true)?;
+    // Below 4000 the canonical form spells thousands with a plain `M`.
+    if thousands < 4 {
+        let (position, character) = high[0];
+        return Err(RomanError::Misplaced {
+            position,
+            character,
+        });
+    }
+    Ok(thousands * 1000 + parse_canonical(&low, false)?)
+}
+
+fn parse_additive(symbols: &[Symbol]) -> Result<u32, RomanError> {
+    let mut value = 0u32;
+    // The next token must be worth less than `limit`, and a subtractive
+    // pair's larger symbol must be less than `symbol_limit`.
+    let (mut limit, mut symbol_limit) = (u32::MAX, u32::MAX);
+    let mut pos = 0;
+    while pos < symbols.len() {
+        let (position, character) = symbols[pos];
+        let current = symbol_value(character).unwrap();
+        let misplaced = RomanError::Misplaced {
+            position,
+            character,
+        };
+        let pair = symbols
+            .get(pos + 1)
+            .map(|&(_, next)| symbol_value(next).unwrap())
+            .filter(|&next| {
+                matches!(current, 1 | 10 | 100) && next / current <= 10 && next > current
+            });
+        match pair {
+            Some(next) => {
+                if next >= symbol_limit || next - current >= limit {
+                    return Err(misplaced);
                 }
-                1.. => {
-                    inner.push_str("I".repeat(num as usize).as_str());
-                    num = 0;
+                value = value.saturating_add(next - current);
+                (limit, symbol_limit) = (current, current + 1);
+                pos += 2;
+            }
+            None => {
+                if current >= limit {
+                    return Err(misplaced);
                 }
-                _ => panic!(),
+                value = value.saturating_add(current);
+                // Only powers of ten may repeat.
+                limit = match current {
+                    1 | 10 | 100 | 1000 => current + 1,
+                    _ => current,
+                };
+                symbol_limit = limit;
+                pos += 1;
             }
         }
+    }
+    Ok(value)
+}
 
-        Self(inner)
+fn parse_lenient(s: &str) -> Result<u32, RomanError> {
+    let (high, low) = symbols(&s.to_uppercase())?;
+    let value = parse_additive(&high)?
+        .saturating_mul(1000)
+        .saturating_add(parse_additive(&low)?);
+    match value {
+        ..=MAX => Ok(value),
+        _ => Err(RomanError::OutOfRange(value)),
     }
 }
+
 fn main() {}
 
 #[cfg(test)]
@@ -85,190 +440,344 @@ mod test {
     #[test]
     fn test_1_is_i() {
         let input = 1;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "I";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_2_is_ii() {
         let input = 2;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "II";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_3_is_iii() {
         let input = 3;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "III";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_4_is_iv() {
         let input = 4;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "IV";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_5_is_v() {
         let input = 5;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "V";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_6_is_vi() {
         let input = 6;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "VI";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_9_is_ix() {
         let input = 9;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "IX";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_16_is_xvi() {
         let input = 16;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "XVI";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_27_is_xxvii() {
         let input = 27;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "XXVII";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_48_is_xlviii() {
         let input = 48;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "XLVIII";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_49_is_xlix() {
         let input = 49;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "XLIX";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_59_is_lix() {
         let input = 59;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "LIX";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_66_is_lxvi() {
         let input = 66;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "LXVI";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_93_is_xciii() {
         let input = 93;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "XCIII";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_141_is_cxli() {
         let input = 141;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "CXLI";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_163_is_clxiii() {
         let input = 163;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "CLXIII";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_166_is_clxvi() {
         let input = 166;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "CLXVI";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_402_is_cdii() {
         let input = 402;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "CDII";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_575_is_dlxxv() {
         let input = 575;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "DLXXV";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_666_is_dclxvi() {
         let input = 666;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "DCLXVI";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_911_is_cmxi() {
         let input = 911;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "CMXI";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_1024_is_mxxiv() {
         let input = 1024;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "MXXIV";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_1666_is_mdclxvi() {
         let input = 1666;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "MDCLXVI";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_3000_is_mmm() {
         let input = 3000;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "MMM";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_3001_is_mmmi() {
         let input = 3001;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "MMMI";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_3888_is_mmmdccclxxxviii() {
         let input = 3888;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "MMMDCCCLXXXVIII";
         assert_eq!(output, expected);
     }
     #[test]
     fn test_3999_is_mmmcmxcix() {
         let input = 3999;
-        let output = Roman::from(input).to_string();
+        let output = Roman::try_from(input).unwrap().to_string();
         let expected = "MMMCMXCIX";
         assert_eq!(output, expected);
     }
+    #[test]
+    fn parses_canonical_numerals() {
+        for n in 1..=3999 {
+            let roman = Roman::try_from(n).unwrap();
+            assert_eq!(roman.to_string().parse(), Ok(roman));
+            assert_eq!(
+                Roman::try_from(roman.to_string().as_str()).map(u32::from),
+                Ok(n)
+            );
+        }
+    }
+    #[test]
+    fn strict_mode_rejects_non_canonical_forms() {
+        let misplaced = |position, character| {
+            Err(RomanError::Misplaced {
+                position,
+                character,
+            })
+        };
+        assert_eq!("IIII".parse::<Roman>(), misplaced(3, 'I'));
+        assert_eq!("VX".parse::<Roman>(), misplaced(1, 'X'));
+        assert_eq!("IC".parse::<Roman>(), misplaced(1, 'C'));
+        assert_eq!("MMMM".parse::<Roman>(), misplaced(3, 'M'));
+        assert_eq!("XIIX".parse::<Roman>(), misplaced(3, 'X'));
+        assert_eq!(
+            "mcm".parse::<Roman>(),
+            Err(RomanError::InvalidCharacter {
+                position: 0,
+                character: 'm'
+            })
+        );
+        assert_eq!("".parse::<Roman>(), Err(RomanError::Empty));
+        assert_eq!(
+            "XIZ".parse::<Roman>(),
+            Err(RomanError::InvalidCharacter {
+                position: 2,
+                character: 'Z'
+            })
+        );
+    }
+    #[test]
+    fn lenient_mode_accepts_additive_forms() {
+        let lenient = |s| Roman::parse_with(s, Mode::Lenient).map(|r| r.value());
+        assert_eq!(lenient("IIII"), Ok(4));
+        assert_eq!(lenient("VIIII"), Ok(9));
+        assert_eq!(lenient("MMMM"), Ok(4000));
+        assert_eq!(lenient("mcmxciv"), Ok(1994));
+        assert_eq!(lenient("CMXC"), Ok(990));
+        let misplaced = |position, character| {
+            Err(RomanError::Misplaced {
+                position,
+                character,
+            })
+        };
+        assert_eq!(lenient("VX"), misplaced(1, 'X'));
+        assert_eq!(lenient("IC"), misplaced(1, 'C'));
+        assert_eq!(lenient("VIV"), misplaced(1, 'I'));
+        assert_eq!(lenient("IIV"), misplaced(1, 'I'));
+        assert_eq!(lenient("IXI"), misplaced(2, 'I'));
+        assert_eq!(lenient("DD"), misplaced(1, 'D'));
+    }
+    #[test]
+    fn arithmetic_and_ordering() {
+        let xiv: Roman = "XIV".parse().unwrap();
+        let ix: Roman = "IX".parse().unwrap();
+        assert_eq!((xiv + ix).to_string(), "XXIII");
+        assert_eq!((xiv - ix).to_string(), "V");
+        assert!(ix < xiv);
+        assert_eq!([xiv, ix].iter().max(), Some(&xiv));
+    }
+    #[test]
+    #[should_panic]
+    fn subtraction_cannot_reach_zero() {
+        let _ = Roman(5) - Roman(5);
+    }
+    #[test]
+    fn zero_and_too_large_are_errors() {
+        assert_eq!(Roman::try_from(0), Err(RomanError::Zero));
+        assert_eq!(
+            Roman::try_from(4_000_000),
+            Err(RomanError::OutOfRange(4_000_000))
+        );
+    }
+    #[test]
+    fn large_numbers_use_the_vinculum() {
+        let roman = Roman::try_from(4_000).unwrap();
+        assert_eq!(roman.to_string(), "I\u{305}V\u{305}");
+        assert_eq!(roman.format(Style::Ascii), "_I_V");
+        let roman = Roman::try_from(1_234_567).unwrap();
+        assert_eq!(roman.format(Style::Ascii), "_M_C_C_X_X_X_I_VDLXVII");
+        let roman = Roman::try_from(MAX).unwrap();
+        assert_eq!(roman.format(Style::Ascii), "_M_M_M_C_M_X_C_I_XCMXCIX");
+        assert_eq!(
+            Roman::try_from(3_999).unwrap().format(Style::Ascii),
+            "MMMCMXCIX"
+        );
+    }
+    #[test]
+    fn vinculum_round_trips() {
+        for n in (1..=MAX).step_by(997).chain([4_000, 10_000, MAX]) {
+            let roman = Roman::try_from(n).unwrap();
+            assert_eq!(roman.to_string().parse(), Ok(roman));
+            assert_eq!(roman.format(Style::Ascii).parse(), Ok(roman));
+        }
+    }
+    #[test]
+    fn strict_vinculum_must_be_canonical() {
+        let misplaced = |position, character| {
+            Err(RomanError::Misplaced {
+                position,
+                character,
+            })
+        };
+        // Below 4000 thousands are written with plain `M`.
+        assert_eq!("_I_I".parse::<Roman>(), misplaced(1, 'I'));
+        assert_eq!("_I_VM".parse::<Roman>(), misplaced(4, 'M'));
+        assert_eq!("_IV_I".parse::<Roman>(), misplaced(4, 'I'));
+        assert_eq!(
+            "_".parse::<Roman>(),
+            Err(RomanError::InvalidCharacter {
+                position: 0,
+                character: '_'
+            })
+        );
+        assert_eq!(
+            "\u{305}I".parse::<Roman>(),
+            Err(RomanError::InvalidCharacter {
+                position: 0,
+                character: '\u{305}'
+            })
+        );
+    }
+    #[test]
+    fn lenient_vinculum() {
+        let lenient = |s| Roman::parse_with(s, Mode::Lenient).map(|r| r.value());
+        assert_eq!(lenient("_i_i"), Ok(2_000));
+        assert_eq!(lenient("_I_I_I_IMMMM"), Ok(8_000));
+        assert_eq!(lenient("_M_M_M_MI"), Err(RomanError::OutOfRange(4_000_001)));
+    }
+    #[test]
+    fn checked_arithmetic() {
+        let (x, v) = (Roman(10), Roman(5));
+        assert_eq!(x.checked_add(v), Some(Roman(15)));
+        assert_eq!(x.checked_sub(v), Some(Roman(5)));
+        assert_eq!(v.checked_sub(v), None);
+        assert_eq!(v.checked_sub(x), None);
+        assert_eq!(Roman(MAX).checked_add(Roman(1)), None);
+        assert_eq!(Roman(MAX - 1).checked_add(Roman(1)), Some(Roman(MAX)));
+    }
+    #[test]
+    #[should_panic]
+    fn addition_cannot_exceed_max() {
+        let _ = Roman(MAX) + Roman(1);
+    }
 }
@@ -1,21 +1,8 @@
-mod cipher {
-    pub fn rotate(input: &str, key: u8) -> String {
-        input
-            .chars()
-            .map(|c| match c {
-                'a'..='z' => char::from(((c as u8 - b'a') + key) % 26 + b'a'),
-                'A'..='Z' => char::from(((c as u8 - b'A') + key) % 26 + b'A'),
-                _ => c,
-            })
-            .collect()
-    }
-}
-
 fn main() {}
 
 #[cfg(test)]
 mod test {
-    use super::*;
+    use exercism_rs::cipher::rotational as cipher;
     #[test]
     fn rotate_a_by_0_same_output_as_input() {
         let text = "a";
@@ -1,3 +1,7 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
 pub fn encode(source: &str) -> String {
     let mut result = String::new();
     let mut chars = source.chars().peekable();
@@ -35,6 +39,140 @@ pub fn decode(source: &str) -> String {
     res
 }
 
+#[derive(Debug)]
+pub enum RleError {
+    Io(io::Error),
+    /// A header at `offset` announces more bytes than the input holds.
+    Truncated {
+        offset: u64,
+    },
+    /// `0x80` at `offset`, which PackBits leaves unused.
+    InvalidHeader {
+        offset: u64,
+    },
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RleError::Io(e) => write!(f, "I/O error: {e}"),
+            RleError::Truncated { offset } => {
+                write!(f, "run at offset {offset} is cut short by end of input")
+            }
+            RleError::InvalidHeader { offset } => {
+                write!(f, "invalid header byte 0x80 at offset {offset}")
+            }
+        }
+    }
+}
+
+impl Error for RleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RleError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RleError {
+    fn from(value: io::Error) -> Self {
+        RleError::Io(value)
+    }
+}
+
+const MAX_BLOCK: usize = 128;
+
+/// Binary-safe PackBits encoder. Every block starts with a signed header `n`:
+/// `0..=127` copies the next `n + 1` bytes literally, `-127..=-1` repeats the
+/// next byte `1 - n` times. Memory use is bounded by one 128-byte block.
+pub fn pack<R: Read, W: Write>(input: R, output: W) -> io::Result<()> {
+    let mut output = BufWriter::new(output);
+    let mut literals = Vec::with_capacity(MAX_BLOCK);
+    let mut run: Option<(u8, usize)> = None;
+
+    for byte in BufReader::new(input).bytes() {
+        let byte = byte?;
+        run = match run {
+            Some((b, count)) if b == byte && count < MAX_BLOCK => Some((b, count + 1)),
+            Some((b, count)) => {
+                flush_run(&mut output, &mut literals, b, count)?;
+                Some((byte, 1))
+            }
+            None => Some((byte, 1)),
+        };
+    }
+    if let Some((b, count)) = run {
+        flush_run(&mut output, &mut literals, b, count)?;
+    }
+    flush_literals(&mut output, &mut literals)?;
+    output.flush()
+}
+
+/// Runs shorter than three bytes are cheaper as literals.
+fn flush_run<W: Write>(
+    output: &mut W,
+    literals: &mut Vec<u8>,
+    byte: u8,
+    count: usize,
+) -> io::Result<()> {
+    if count >= 3 {
+        flush_literals(output, literals)?;
+        return output.write_all(&[(257 - count) as u8, byte]);
+    }
+    for _ in 0..count {
+        literals.push(byte);
+        if literals.len() == MAX_BLOCK {
+            flush_literals(output, literals)?;
+        }
+    }
+    Ok(())
+}
+
+fn flush_literals<W: Write>(output: &mut W, literals: &mut Vec<u8>) -> io::Result<()> {
+    if !literals.is_empty() {
+        output.write_all(&[(literals.len() - 1) as u8])?;
+        output.write_all(literals)?;
+        literals.clear();
+    }
+    Ok(())
+}
+
+pub fn unpack<R: Read, W: Write>(input: R, output: W) -> Result<(), RleError> {
+    let mut input = BufReader::new(input);
+    let mut output = BufWriter::new(output);
+    let mut block = [0; MAX_BLOCK];
+    let mut offset = 0;
+
+    loop {
+        let mut header = [0];
+        if input.read(&mut header)? == 0 {
+            break;
+        }
+        let truncated = |e: io::Error| match e.kind() {
+            io::ErrorKind::UnexpectedEof => RleError::Truncated { offset },
+            _ => RleError::Io(e),
+        };
+        match header[0] as i8 {
+            -128 => return Err(RleError::InvalidHeader { offset }),
+            n @ 0.. => {
+                let len = n as usize + 1;
+                input.read_exact(&mut block[..len]).map_err(truncated)?;
+                output.write_all(&block[..len])?;
+                offset += 1 + len as u64;
+            }
+            n => {
+                input.read_exact(&mut block[..1]).map_err(truncated)?;
+                let len = 1 - n as isize;
+                output.write_all(&[block[0]].repeat(len as usize))?;
+                offset += 2;
+            }
+        }
+    }
+    output.flush()?;
+    Ok(())
+}
+
 fn main() {
     println!("{:?}", decode("2A3B4C"));
     println!("{:?}", "dd".repeat(0));
@@ -134,4 +272,74 @@ mod test {
         let expected = "zzz ZZ  zZ";
         assert_eq!(output, expected);
     }
+    fn packed(input: &[u8]) -> Vec<u8> {
+        let mut output = vec![];
+        pack(input, &mut output).unwrap();
+        output
+    }
+    fn unpacked(input: &[u8]) -> Result<Vec<u8>, RleError> {
+        let mut output = vec![];
+        unpack(input, &mut output).map(|_| output)
+    }
+    #[test]
+    fn pack_matches_reference_packbits_output() {
+        let input = [
+            0xAA, 0xAA, 0xAA, 0x80, 0x00, 0x2A, 0xAA, 0xAA, 0xAA, 0xAA, 0x80, 0x00, 0x2A, 0x22,
+            0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA,
+        ];
+        let expected = [
+            0xFE, 0xAA, 0x02, 0x80, 0x00, 0x2A, 0xFD, 0xAA, 0x03, 0x80, 0x00, 0x2A, 0x22, 0xF7,
+            0xAA,
+        ];
+        assert_eq!(packed(&input), expected);
+        assert_eq!(unpacked(&expected).unwrap(), input);
+    }
+    #[test]
+    fn digits_round_trip_unambiguously() {
+        let input = b"2A3B4C";
+        assert_eq!(unpacked(&packed(input)).unwrap(), input);
+    }
+    #[test]
+    fn empty_input() {
+        assert_eq!(packed(b""), b"");
+        assert_eq!(unpacked(b"").unwrap(), b"");
+    }
+    #[test]
+    fn long_runs_and_literals_are_split_into_blocks() {
+        let mut input = vec![7; 300];
+        input.extend((0..=255).cycle().take(300));
+        let output = packed(&input);
+        assert_eq!(&output[..6], [0x81, 7, 0x81, 7, 0xD5, 7]);
+        assert_eq!(unpacked(&output).unwrap(), input);
+    }
+    #[test]
+    fn truncated_input_is_an_error() {
+        assert!(matches!(
+            unpacked(&[0x02, b'a', b'b']),
+            Err(RleError::Truncated { offset: 0 })
+        ));
+        assert!(matches!(
+            unpacked(&[0x00, b'a', 0xFE]),
+            Err(RleError::Truncated { offset: 2 })
+        ));
+    }
+    #[test]
+    fn invalid_header_is_an_error() {
+        assert!(matches!(
+            unpacked(&[0xFE, b'a', 0x80]),
+            Err(RleError::InvalidHeader { offset: 2 })
+        ));
+    }
+    #[test]
+    fn errors_convert_into_boxed_errors() {
+        fn unpack_boxed(input: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+            Ok(unpacked(input)?)
+        }
+        let error = unpack_boxed(&[0xFE, b'a', 0x80]).unwrap_err();
+        assert_eq!(error.to_string(), "invalid header byte 0x80 at offset 2");
+        assert!(error.source().is_none());
+        let error = RleError::from(io::Error::other("disk on fire"));
+        assert_eq!(error.to_string(), "I/O error: disk on fire");
+        assert!(error.source().is_some());
+    }
 }
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::iter::successors;
 
 const NUMBERS: [&str; 100] = [
@@ -113,49 +114,303 @@ const UNITS: [&str; 7] = [
     "quintillion",
 ];
 
-// if n >= 1_000_000_000_000_000_000 {
-// if n >= 1_000_000_000_000_000 {
-// if n >= 1_000_000_000_000 {
-// if n >= 1_000_000_000 {
-// if n >= 1_000_000 {
-// if n >= 1_000 {
-//     let m = n / 1_000;
-//     n %= 1_000;
-//     output.push(encode(m));
-//     output.push(UNITS[1].to_string());
-// }
+/// How numbers are spelled out. The default matches [`encode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// "twenty-one" rather than "twenty one".
+    pub hyphenate: bool,
+    /// British style: "one hundred and one", "one thousand and five".
+    pub british: bool,
+}
 
-macro_rules! handle {
-    ($target:expr,$bit:expr, $n:ident, $output:ident) => {
-        if $n >= $target {
-            let m = $n / $target;
-            $n %= $target;
-            $output.push(encode(m));
-            $output.push(UNITS[$bit as usize].to_string());
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            hyphenate: true,
+            british: false,
         }
+    }
+}
+
+/// A currency's unit names, singular then plural.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Currency {
+    pub major: (&'static str, &'static str),
+    pub minor: (&'static str, &'static str),
+}
+
+impl Currency {
+    pub const DOLLARS: Currency = Currency {
+        major: ("dollar", "dollars"),
+        minor: ("cent", "cents"),
+    };
+    pub const EUROS: Currency = Currency {
+        major: ("euro", "euros"),
+        minor: ("cent", "cents"),
+    };
+    pub const POUNDS: Currency = Currency {
+        major: ("pound", "pounds"),
+        minor: ("penny", "pence"),
     };
 }
 
-pub fn encode(mut n: u64) -> String {
-    let mut output = vec![];
+pub fn encode(n: u64) -> String {
+    encode_with(n, &Options::default())
+}
+
+pub fn encode_with(n: u64, options: &Options) -> String {
     if n == 0 {
         return "zero".to_string();
     }
-    while n > 0 {
-        for i in (0..=6).rev() {
-            let target = 10_u64.pow((3 * i).max(2));
-            handle!(target, i, n, output);
-        }
 
-        if n != 0 {
-            output.push(NUMBERS[n as usize].to_string());
-            n = 0;
+    let groups = successors(Some(n), |&m| Some(m / 1000))
+        .take_while(|&m| m > 0)
+        .map(|m| (m % 1000) as usize)
+        .collect::<Vec<_>>();
+
+    let mut output = vec![];
+    for (scale, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        let (hundreds, rest) = (group / 100, group % 100);
+        if hundreds > 0 {
+            output.push(NUMBERS[hundreds].to_string());
+            output.push(UNITS[0].to_string());
+        }
+        if rest > 0 {
+            if options.british && (hundreds > 0 || (scale == 0 && n >= 1000)) {
+                output.push("and".to_string());
+            }
+            let words = NUMBERS[rest];
+            match options.hyphenate {
+                true => output.push(words.to_string()),
+                false => output.push(words.replace('-', " ")),
+            }
+        }
+        if scale > 0 {
+            output.push(UNITS[scale].to_string());
         }
     }
 
     output.join(" ")
 }
 
+pub fn encode_signed(n: i64, options: &Options) -> String {
+    let words = encode_with(n.unsigned_abs(), options);
+    match n < 0 {
+        true => format!("negative {words}"),
+        false => words,
+    }
+}
+
+/// "first", "twenty-second", "one hundredth", "one millionth".
+pub fn encode_ordinal(n: u64, options: &Options) -> String {
+    let cardinal = encode_with(n, options);
+    let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+    let (head, last) = cardinal.split_at(split);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        word if word.ends_with('y') => format!("{}ieth", &word[..word.len() - 1]),
+        word => format!("{word}th"),
+    };
+    format!("{head}{last}")
+}
+
+/// Reads out a decimal literal digit by digit after the point:
+/// "-3.14" is "negative three point one four". `None` if `s` is malformed.
+pub fn encode_decimal(s: &str, options: &Options) -> Option<String> {
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (whole, fraction) = match unsigned.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (unsigned, None),
+    };
+    if !whole.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let mut output = vec![];
+    if negative {
+        output.push("negative".to_string());
+    }
+    output.push(encode_with(whole.parse().ok()?, options));
+    if let Some(fraction) = fraction {
+        if fraction.is_empty() {
+            return None;
+        }
+        output.push("point".to_string());
+        for c in fraction.chars() {
+            output.push(NUMBERS[c.to_digit(10)? as usize].to_string());
+        }
+    }
+
+    Some(output.join(" "))
+}
+
+/// An amount given in minor units, e.g. `305` in dollars is
+/// "three dollars and five cents".
+pub fn encode_currency(minor_units: i64, currency: &Currency, options: &Options) -> String {
+    let amount = minor_units.unsigned_abs();
+    let (major, minor) = (amount / 100, amount % 100);
+    let unit = |n: u64, (one, many): (&str, &str)| match n {
+        1 => format!("one {one}"),
+        n => format!("{} {many}", encode_with(n, options)),
+    };
+
+    let words = match (major, minor) {
+        (_, 0) => unit(major, currency.major),
+        (0, _) => unit(minor, currency.minor),
+        _ => format!(
+            "{} and {}",
+            unit(major, currency.major),
+            unit(minor, currency.minor)
+        ),
+    };
+    match minor_units < 0 {
+        true => format!("negative {words}"),
+        false => words,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SayError {
+    Empty,
+    UnknownWord(String),
+    UnexpectedWord(String),
+    Overflow,
+}
+
+impl fmt::Display for SayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SayError::Empty => write!(f, "no number words given"),
+            SayError::UnknownWord(word) => write!(f, "`{word}` is not a number word"),
+            SayError::UnexpectedWord(word) => write!(f, "`{word}` is out of place"),
+            SayError::Overflow => write!(f, "number does not fit in a u64"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Word {
+    /// "a", as in "a hundred" or "a million".
+    A,
+    /// zero to nineteen.
+    Unit(u64),
+    /// twenty, thirty, ... ninety.
+    Tens(u64),
+    Hundred,
+    /// thousand (1) up to quintillion (6), as a power of 1000.
+    Scale(u32),
+}
+
+impl Word {
+    fn parse(word: &str) -> Option<Word> {
+        if word == "a" {
+            return Some(Word::A);
+        }
+        if let Some(n) = NUMBERS[..20].iter().position(|&w| w == word) {
+            return Some(Word::Unit(n as u64));
+        }
+        if let Some(n) = NUMBERS[20..].iter().step_by(10).position(|&w| w == word) {
+            return Some(Word::Tens(20 + 10 * n as u64));
+        }
+        match UNITS.iter().position(|&w| w == word)? {
+            0 => Some(Word::Hundred),
+            i => Some(Word::Scale(i as u32)),
+        }
+    }
+}
+
+/// Parse one group below a thousand, e.g. "three hundred and forty-two",
+/// starting at `*i`. Returns `None` if no word was consumed.
+fn decode_group(words: &[Word], i: &mut usize) -> Option<u64> {
+    let start = *i;
+    let mut value = 0;
+    match words[*i..] {
+        [Word::A, Word::Hundred, ..] => {
+            value = 100;
+            *i += 2;
+        }
+        [Word::Unit(n @ 1..=9), Word::Hundred, ..] => {
+            value = n * 100;
+            *i += 2;
+        }
+        _ => {}
+    }
+    match words[*i..] {
+        [Word::Tens(t), Word::Unit(u @ 1..=9), ..] => {
+            value += t + u;
+            *i += 2;
+        }
+        [Word::Tens(t), ..] => {
+            value += t;
+            *i += 1;
+        }
+        [Word::Unit(u @ 1..), ..] => {
+            value += u;
+            *i += 1;
+        }
+        [Word::A, Word::Scale(_), ..] if *i == start => {
+            value = 1;
+            *i += 1;
+        }
+        _ => {}
+    }
+    (*i > start).then_some(value)
+}
+
+/// The inverse of [`encode`]. Also accepts capitalisation, "and", commas,
+/// spaces in place of hyphens and "a" in place of "one" before a scale.
+pub fn decode(s: &str) -> Result<u64, SayError> {
+    let lower = s.to_lowercase();
+    let tokens = lower
+        .split(|c: char| c.is_whitespace() || c == '-' || c == ',')
+        .filter(|w| !w.is_empty() && *w != "and")
+        .collect::<Vec<_>>();
+    let words = tokens
+        .iter()
+        .map(|&w| Word::parse(w).ok_or_else(|| SayError::UnknownWord(w.to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match words[..] {
+        [] => return Err(SayError::Empty),
+        [Word::Unit(0)] => return Ok(0),
+        _ => {}
+    }
+
+    let unexpected = |i: usize| SayError::UnexpectedWord(tokens[i].to_string());
+    let mut total = 0u64;
+    let mut last_scale = u32::MAX;
+    let mut i = 0;
+    while i < words.len() {
+        let group = decode_group(&words, &mut i).ok_or_else(|| unexpected(i))?;
+        let scale = match words.get(i) {
+            Some(&Word::Scale(scale)) if scale < last_scale => scale,
+            Some(_) => return Err(unexpected(i)),
+            None => 0,
+        };
+        let value = 1000u64
+            .checked_pow(scale)
+            .and_then(|m| m.checked_mul(group))
+            .ok_or(SayError::Overflow)?;
+        total = total.checked_add(value).ok_or(SayError::Overflow)?;
+        last_scale = scale;
+        i += 1;
+    }
+
+    Ok(total)
+}
+
 fn main() {
     let ex: Vec<(u64, &str)> = successors(Some(1000_u64), |v| v.checked_mul(1000))
         .zip(UNITS.iter().cloned())
@@ -300,4 +555,184 @@ mod test {
         let expected = "eighteen quintillion four hundred forty-six quadrillion seven hundred forty-four trillion seventy-three billion seven hundred nine million five hundred fifty-one thousand six hundred fifteen";
         assert_eq!(output, expected);
     }
+    #[test]
+    fn decode_inverts_encode() {
+        assert_eq!(decode("zero"), Ok(0));
+        assert_eq!(decode("ninety-nine"), Ok(99));
+        assert_eq!(
+            decode("one million two thousand three hundred forty-five"),
+            Ok(1_002_345)
+        );
+        assert_eq!(decode(&encode(u64::MAX)), Ok(u64::MAX));
+    }
+    #[test]
+    fn decode_accepts_common_variants() {
+        assert_eq!(decode("One Hundred and One"), Ok(101));
+        assert_eq!(decode("a hundred"), Ok(100));
+        assert_eq!(decode("a thousand and twenty five"), Ok(1_025));
+        assert_eq!(decode("Twenty One"), Ok(21));
+        assert_eq!(decode("two million, three hundred thousand"), Ok(2_300_000));
+    }
+    #[test]
+    fn decode_rejects_malformed_input() {
+        assert_eq!(decode(""), Err(SayError::Empty));
+        assert_eq!(decode("and"), Err(SayError::Empty));
+        assert_eq!(
+            decode("one zillion"),
+            Err(SayError::UnknownWord("zillion".to_string()))
+        );
+        assert_eq!(
+            decode("one two"),
+            Err(SayError::UnexpectedWord("two".to_string()))
+        );
+        assert_eq!(
+            decode("hundred"),
+            Err(SayError::UnexpectedWord("hundred".to_string()))
+        );
+        assert_eq!(
+            decode("one thousand one million"),
+            Err(SayError::UnexpectedWord("million".to_string()))
+        );
+        assert_eq!(
+            decode("zero thousand"),
+            Err(SayError::UnexpectedWord("zero".to_string()))
+        );
+        assert_eq!(
+            decode("twenty thirty"),
+            Err(SayError::UnexpectedWord("thirty".to_string()))
+        );
+    }
+    #[test]
+    fn decode_detects_overflow() {
+        assert_eq!(decode("nineteen quintillion"), Err(SayError::Overflow));
+        assert_eq!(
+            decode("eighteen quintillion nine hundred quadrillion"),
+            Err(SayError::Overflow)
+        );
+    }
+    #[test]
+    fn round_trips_random_numbers() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(45);
+        for _ in 0..10_000 {
+            // Spread the samples over every magnitude, not just the top one.
+            let n = rng.random::<u64>() >> rng.random_range(0..64);
+            assert_eq!(decode(&encode(n)), Ok(n), "{n}");
+            assert_eq!(decode(&encode(n).to_uppercase().replace('-', " ")), Ok(n));
+        }
+    }
+    #[test]
+    fn default_options_match_encode() {
+        for n in [0, 7, 101, 1_005, 987_654_321_123, u64::MAX] {
+            assert_eq!(encode_with(n, &Options::default()), encode(n));
+        }
+    }
+    #[test]
+    fn unhyphenated_tens() {
+        let options = Options {
+            hyphenate: false,
+            ..Options::default()
+        };
+        assert_eq!(
+            encode_with(42_021, &options),
+            "forty two thousand twenty one"
+        );
+    }
+    #[test]
+    fn british_and() {
+        let options = Options {
+            british: true,
+            ..Options::default()
+        };
+        assert_eq!(encode_with(101, &options), "one hundred and one");
+        assert_eq!(encode_with(1_005, &options), "one thousand and five");
+        assert_eq!(encode_with(100, &options), "one hundred");
+        assert_eq!(
+            encode_with(2_000_120, &options),
+            "two million one hundred and twenty"
+        );
+        assert_eq!(
+            encode_with(1_234, &options),
+            "one thousand two hundred and thirty-four"
+        );
+        assert_eq!(decode(&encode_with(1_005, &options)), Ok(1_005));
+    }
+    #[test]
+    fn negatives() {
+        let options = Options::default();
+        assert_eq!(encode_signed(-5, &options), "negative five");
+        assert_eq!(encode_signed(0, &options), "zero");
+        assert_eq!(
+            encode_signed(i64::MIN, &options),
+            format!("negative {}", encode(1 << 63))
+        );
+    }
+    #[test]
+    fn ordinals() {
+        let options = Options::default();
+        let ordinal = |n| encode_ordinal(n, &options);
+        assert_eq!(ordinal(0), "zeroth");
+        assert_eq!(ordinal(1), "first");
+        assert_eq!(ordinal(12), "twelfth");
+        assert_eq!(ordinal(20), "twentieth");
+        assert_eq!(ordinal(21), "twenty-first");
+        assert_eq!(ordinal(103), "one hundred third");
+        assert_eq!(ordinal(100), "one hundredth");
+        assert_eq!(ordinal(1_000_000), "one millionth");
+        let options = Options {
+            hyphenate: false,
+            british: true,
+        };
+        assert_eq!(
+            encode_ordinal(158, &options),
+            "one hundred and fifty eighth"
+        );
+    }
+    #[test]
+    fn decimals() {
+        let options = Options::default();
+        assert_eq!(
+            encode_decimal("-3.14", &options).as_deref(),
+            Some("negative three point one four")
+        );
+        assert_eq!(
+            encode_decimal("20.05", &options).as_deref(),
+            Some("twenty point zero five")
+        );
+        assert_eq!(encode_decimal("7", &options).as_deref(), Some("seven"));
+        assert_eq!(encode_decimal("1.", &options), None);
+        assert_eq!(encode_decimal(".5", &options), None);
+        assert_eq!(encode_decimal("1e3", &options), None);
+        assert_eq!(encode_decimal("+1", &options), None);
+    }
+    #[test]
+    fn currency_amounts() {
+        let options = Options::default();
+        assert_eq!(
+            encode_currency(305, &Currency::DOLLARS, &options),
+            "three dollars and five cents"
+        );
+        assert_eq!(
+            encode_currency(100, &Currency::DOLLARS, &options),
+            "one dollar"
+        );
+        assert_eq!(encode_currency(1, &Currency::EUROS, &options), "one cent");
+        assert_eq!(
+            encode_currency(0, &Currency::DOLLARS, &options),
+            "zero dollars"
+        );
+        assert_eq!(
+            encode_currency(-250, &Currency::POUNDS, &options),
+            "negative two pounds and fifty pence"
+        );
+        let options = Options {
+            british: true,
+            ..Options::default()
+        };
+        assert_eq!(
+            encode_currency(10_199, &Currency::POUNDS, &options),
+            "one hundred and one pounds and ninety-nine pence"
+        );
+    }
 }
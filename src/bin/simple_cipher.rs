@@ -1,38 +1,4 @@
-pub fn encode(key: &str, s: &str) -> Option<String> {
-    let mut key = key.chars().cycle();
-    s.chars()
-        .map(|c| match key.next() {
-            Some(k) if k.is_ascii_lowercase() => Some(char::from(
-                ((c as u8 - b'a' + (k as u8 - b'a')) % 26) + b'a',
-            )),
-            _ => None,
-        })
-        .collect()
-}
-
-pub fn decode(key: &str, s: &str) -> Option<String> {
-    let mut key = key.chars().cycle();
-    s.chars()
-        .map(|c| match key.next() {
-            Some(k) if k.is_ascii_lowercase() => Some(char::from(
-                (((c as u8 - b'a') as i32 - ((k as u8 - b'a') as i32) + 26) as u8 % 26) + b'a',
-            )),
-            _ => None,
-        })
-        .collect()
-}
-
-pub fn encode_random(s: &str) -> (String, String) {
-    let key = (0..100)
-        .map(|_| rand::random_range('a'..='z'))
-        .collect::<String>();
-    let mut k = key.chars().cycle();
-    let encode = s
-        .chars()
-        .map(|c| char::from(((c as u8 - b'a' + (k.next().unwrap() as u8 - b'a')) % 26) + b'a'))
-        .collect();
-    (key, encode)
-}
+use exercism_rs::cipher::simple::*;
 
 fn main() {
     let (k, encoded) = encode_random("aaaaaaaaaa");
@@ -42,6 +8,8 @@ fn main() {
 #[cfg(test)]
 mod test {
     use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
     use std::collections::HashSet;
     const PLAIN_TEXT: &str = "thisismysecret";
     const KEY: &str = "abcdefghij";
@@ -165,4 +133,72 @@ mod test {
         let (k, encoded) = encode_random(PLAIN_TEXT);
         assert_eq!(decode(&k, &encoded), Some(PLAIN_TEXT.to_string()));
     }
+    #[test]
+    fn typed_errors_for_invalid_input() {
+        assert_eq!(
+            Vigenere::new("").encode("abc"),
+            Err(SimpleCipherError::EmptyKey)
+        );
+        assert_eq!(
+            Vigenere::new("abC").encode("abc"),
+            Err(SimpleCipherError::InvalidKeyChar('C'))
+        );
+        assert_eq!(
+            Vigenere::new("abc").encode("ab c"),
+            Err(SimpleCipherError::InvalidTextChar {
+                position: 2,
+                char: ' '
+            })
+        );
+    }
+    #[test]
+    fn invalid_plaintext_does_not_panic() {
+        assert_eq!(encode(KEY, "Hello, wörld"), None);
+        assert_eq!(decode(KEY, "HELLO"), None);
+    }
+    #[test]
+    fn pass_through_keeps_non_letters() {
+        let cipher = Vigenere::new("b").with_pass_through();
+        assert_eq!(
+            cipher.encode("Hello, wörld!"),
+            Ok("Ifmmp, xösme!".to_string())
+        );
+        assert_eq!(
+            cipher.decode("Ifmmp, xösme!"),
+            Ok("Hello, wörld!".to_string())
+        );
+    }
+    #[test]
+    fn pass_through_does_not_consume_key_letters() {
+        let cipher = Vigenere::new("ab").with_pass_through();
+        assert_eq!(cipher.encode("a a a"), Ok("a b a".to_string()));
+    }
+    #[test]
+    fn encode_random_accepts_any_text() {
+        let (k, encoded) = encode_random("Hello, World");
+        assert_eq!(
+            Vigenere::new(&k).with_pass_through().decode(&encoded),
+            Ok("Hello, World".to_string())
+        );
+    }
+    #[test]
+    fn seeded_random_keys_are_reproducible() {
+        let mut a = StdRng::seed_from_u64(42);
+        let mut b = StdRng::seed_from_u64(42);
+        let (key_a, encoded_a) = encode_random_with(&mut a, 12, PLAIN_TEXT).unwrap();
+        let (key_b, encoded_b) = encode_random_with(&mut b, 12, PLAIN_TEXT).unwrap();
+        assert_eq!(key_a.len(), 12);
+        assert_eq!((key_a, encoded_a), (key_b, encoded_b));
+    }
+    #[test]
+    fn encode_random_with_rejects_invalid_text() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(
+            encode_random_with(&mut rng, 5, "a1"),
+            Err(SimpleCipherError::InvalidTextChar {
+                position: 1,
+                char: '1'
+            })
+        );
+    }
 }
@@ -1,12 +1,58 @@
+use std::cmp::Reverse;
 use std::collections::HashMap;
-const HEADER: &str = "Team                           | MP |  W |  D |  L |  P";
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::{env, fs, io, process};
 
-#[derive(Default)]
+const COLUMNS: [&str; 6] = ["Team", "MP", "W", "D", "L", "P"];
+const MIN_NAME_WIDTH: usize = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TieBreaker {
+    GoalDifference,
+    GoalsScored,
+    HeadToHead,
+}
+
+pub struct Rules {
+    pub win: usize,
+    pub draw: usize,
+    pub loss: usize,
+    /// Applied in order to teams level on points; any remaining tie is broken by name.
+    pub tie_breakers: Vec<TieBreaker>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            win: 3,
+            draw: 1,
+            loss: 0,
+            tie_breakers: vec![],
+        }
+    }
+}
+
+impl Rules {
+    fn points(&self, m: MatchResult) -> usize {
+        match m {
+            MatchResult::WIN => self.win,
+            MatchResult::DRAW => self.draw,
+            MatchResult::LOSS => self.loss,
+        }
+    }
+}
+
+#[derive(Default, Clone)]
 pub struct Team {
     name: String,
     win: usize,
     draw: usize,
     loss: usize,
+    points: usize,
+    goals_for: usize,
+    goals_against: usize,
 }
 
 impl Team {
@@ -18,37 +64,154 @@ impl Team {
     }
 
     fn score(&self) -> usize {
-        self.win * 3 + self.draw
+        self.points
     }
 
     fn matchs(&self) -> usize {
         self.win + self.draw + self.loss
     }
 
-    fn add_match(&mut self, m: MatchResult) {
+    fn goal_difference(&self) -> i64 {
+        self.goals_for as i64 - self.goals_against as i64
+    }
+
+    fn add_match(&mut self, m: MatchResult, goals: Option<(usize, usize)>, rules: &Rules) {
         match m {
             MatchResult::WIN => self.win += 1,
             MatchResult::DRAW => self.draw += 1,
             MatchResult::LOSS => self.loss += 1,
         }
+        self.points += rules.points(m);
+        if let Some((scored, conceded)) = goals {
+            self.goals_for += scored;
+            self.goals_against += conceded;
+        }
     }
 }
 
-impl From<Team> for String {
-    fn from(value: Team) -> Self {
-        format!(
-            "{:31}| {:2} | {:2} | {:2} | {:2} | {:2}",
-            value.name,
-            value.matchs(),
-            value.win,
-            value.draw,
-            value.loss,
-            value.score()
-        )
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!("unknown format `{s}`")),
+        }
+    }
+}
+
+/// Ranked teams, ready to be rendered in any `Format`.
+pub struct Standings {
+    teams: Vec<Team>,
+}
+
+impl Standings {
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text => self.to_text(),
+            Format::Csv => self.to_csv(),
+            Format::Json => self.to_json(),
+            Format::Markdown => self.to_markdown(),
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = (&str, [usize; 5])> {
+        self.teams.iter().map(|t| {
+            (
+                t.name.as_str(),
+                [t.matchs(), t.win, t.draw, t.loss, t.score()],
+            )
+        })
+    }
+
+    fn to_text(&self) -> String {
+        let width = self
+            .teams
+            .iter()
+            .map(|t| t.name.chars().count())
+            .fold(MIN_NAME_WIDTH, usize::max)
+            + 1;
+        let line = |[name, mp, w, d, l, p]: [String; 6]| {
+            format!("{name:width$}| {mp:>2} | {w:>2} | {d:>2} | {l:>2} | {p:>2}")
+        };
+        vec![line(COLUMNS.map(String::from))]
+            .into_iter()
+            .chain(self.rows().map(|(name, cols)| {
+                let [mp, w, d, l, p] = cols.map(|n| n.to_string());
+                line([name.to_string(), mp, w, d, l, p])
+            }))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn to_csv(&self) -> String {
+        let escape = |name: &str| {
+            if name.contains([',', '"', '\n']) {
+                format!("\"{}\"", name.replace('"', "\"\""))
+            } else {
+                name.to_string()
+            }
+        };
+        vec![COLUMNS.join(",")]
+            .into_iter()
+            .chain(self.rows().map(|(name, cols)| {
+                let cols = cols.map(|n| n.to_string()).join(",");
+                format!("{},{cols}", escape(name))
+            }))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn to_json(&self) -> String {
+        let escape = |name: &str| {
+            name.chars()
+                .map(|c| match c {
+                    '"' => "\\\"".to_string(),
+                    '\\' => "\\\\".to_string(),
+                    c if (c as u32) < 0x20 => format!("\\u{:04x}", c as u32),
+                    c => c.to_string(),
+                })
+                .collect::<String>()
+        };
+        let rows = self
+            .rows()
+            .map(|(name, [mp, w, d, l, p])| {
+                format!(
+                    "{{\"team\":\"{}\",\"played\":{mp},\"won\":{w},\"drawn\":{d},\"lost\":{l},\"points\":{p}}}",
+                    escape(name)
+                )
+            })
+            .collect::<Vec<_>>();
+        format!("[{}]", rows.join(","))
+    }
+
+    fn to_markdown(&self) -> String {
+        vec![
+            format!("| {} |", COLUMNS.join(" | ")),
+            "| --- | ---: | ---: | ---: | ---: | ---: |".to_string(),
+        ]
+        .into_iter()
+        .chain(self.rows().map(|(name, cols)| {
+            let cols = cols.map(|n| n.to_string()).join(" | ");
+            format!("| {} | {cols} |", name.replace('|', "\\|"))
+        }))
+        .collect::<Vec<_>>()
+        .join("\n")
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MatchResult {
     WIN,
     DRAW,
@@ -65,53 +228,471 @@ impl MatchResult {
     }
 }
 
+impl MatchResult {
+    fn from_goals(home: usize, away: usize) -> Self {
+        match home.cmp(&away) {
+            std::cmp::Ordering::Greater => MatchResult::WIN,
+            std::cmp::Ordering::Equal => MatchResult::DRAW,
+            std::cmp::Ordering::Less => MatchResult::LOSS,
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "win" => Some(MatchResult::WIN),
+            "draw" => Some(MatchResult::DRAW),
+            "loss" => Some(MatchResult::LOSS),
+            _ => None,
+        }
+    }
+}
+
 impl From<&str> for MatchResult {
     fn from(value: &str) -> Self {
-        match value {
-            "win" => MatchResult::WIN,
-            "draw" => MatchResult::DRAW,
-            "loss" => MatchResult::LOSS,
-            _ => panic!(),
+        MatchResult::parse(value).unwrap()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LineError {
+    MissingFields(usize),
+    EmptyTeamName,
+    UnknownResult(String),
+    SelfMatch(String),
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineError::MissingFields(n) => write!(f, "expected 3 fields, found {n}"),
+            LineError::EmptyTeamName => write!(f, "team name is empty"),
+            LineError::UnknownResult(r) => write!(f, "unknown result `{r}`"),
+            LineError::SelfMatch(t) => write!(f, "`{t}` cannot play itself"),
+        }
+    }
+}
+
+/// Every malformed line of the input, keyed by its 1-based line number.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub lines: Vec<(usize, LineError)>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self
+            .lines
+            .iter()
+            .map(|(n, e)| format!("line {n}: {e}"))
+            .collect::<Vec<_>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl Error for LineError {}
+
+impl Error for ParseError {
+    /// The first malformed line; all of them are in `lines`.
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.lines.first().map(|(_, e)| e as &(dyn Error + 'static))
+    }
+}
+
+struct Match<'a> {
+    home: &'a str,
+    away: &'a str,
+    result: MatchResult,
+    goals: Option<(usize, usize)>,
+}
+
+impl<'a> Match<'a> {
+    fn parse(line: &'a str) -> Result<Self, LineError> {
+        let curr = line.splitn(3, ";").collect::<Vec<&str>>();
+        if curr.len() < 3 {
+            return Err(LineError::MissingFields(curr.len()));
+        }
+        if curr[0].is_empty() || curr[1].is_empty() {
+            return Err(LineError::EmptyTeamName);
+        }
+        if curr[0] == curr[1] {
+            return Err(LineError::SelfMatch(curr[0].to_string()));
         }
+        let (result, goals) = match parse_scoreline(curr[2]) {
+            Some((home, away)) => (MatchResult::from_goals(home, away), Some((home, away))),
+            None => match MatchResult::parse(curr[2]) {
+                Some(result) => (result, None),
+                None => return Err(LineError::UnknownResult(curr[2].to_string())),
+            },
+        };
+        Ok(Self {
+            home: curr[0],
+            away: curr[1],
+            result,
+            goals,
+        })
+    }
+}
+
+fn parse_scoreline(value: &str) -> Option<(usize, usize)> {
+    let (home, away) = value.split_once('-')?;
+    Some((home.trim().parse().ok()?, away.trim().parse().ok()?))
+}
+
+fn rank(mut teams: Vec<Team>, matches: &[Match], rules: &Rules) -> Vec<Team> {
+    teams.sort_by_key(|t| Reverse(t.score()));
+    teams
+        .chunk_by(|a, b| a.score() == b.score())
+        .flat_map(|group| break_ties(group.to_vec(), matches, rules, &rules.tie_breakers))
+        .collect()
+}
+
+fn break_ties(
+    mut group: Vec<Team>,
+    matches: &[Match],
+    rules: &Rules,
+    tie_breakers: &[TieBreaker],
+) -> Vec<Team> {
+    let Some((breaker, rest)) = tie_breakers.split_first() else {
+        group.sort_by(|a, b| a.name.cmp(&b.name));
+        return group;
+    };
+    if group.len() < 2 {
+        return group;
     }
+
+    let key = |team: &Team| match breaker {
+        TieBreaker::GoalDifference => team.goal_difference(),
+        TieBreaker::GoalsScored => team.goals_for as i64,
+        TieBreaker::HeadToHead => matches
+            .iter()
+            .filter(|m| group.iter().any(|t| t.name == m.home))
+            .filter(|m| group.iter().any(|t| t.name == m.away))
+            .map(|m| {
+                if m.home == team.name {
+                    rules.points(m.result) as i64
+                } else if m.away == team.name {
+                    rules.points(m.result.reverse()) as i64
+                } else {
+                    0
+                }
+            })
+            .sum(),
+    };
+    let mut keyed = group
+        .iter()
+        .map(|t| (key(t), t.clone()))
+        .collect::<Vec<_>>();
+    keyed.sort_by_key(|(k, _)| Reverse(*k));
+
+    keyed
+        .chunk_by(|a, b| a.0 == b.0)
+        .flat_map(|tied| {
+            let tied = tied.iter().map(|(_, t)| t.clone()).collect();
+            break_ties(tied, matches, rules, rest)
+        })
+        .collect()
 }
 
 pub fn tally(match_results: &str) -> String {
-    let mut teams = match_results
+    tally_with(match_results, &Rules::default())
+}
+
+pub fn tally_with(match_results: &str, rules: &Rules) -> String {
+    try_tally_with(match_results, rules).unwrap()
+}
+
+pub fn try_tally(match_results: &str) -> Result<String, ParseError> {
+    try_tally_with(match_results, &Rules::default())
+}
+
+pub fn try_tally_with(match_results: &str, rules: &Rules) -> Result<String, ParseError> {
+    standings(match_results, rules).map(|s| s.render(Format::Text))
+}
+
+pub fn standings(match_results: &str, rules: &Rules) -> Result<Standings, ParseError> {
+    let (matches, errors): (Vec<_>, Vec<_>) = match_results
         .lines()
-        .map(|line| line.splitn(3, ";").collect::<Vec<&str>>())
-        .fold(HashMap::new(), |mut h, curr| {
-            let t1 = curr[0];
-            let t2 = curr[1];
-            let result = curr[2].into();
-
-            h.entry(t1)
-                .or_insert(Team::new(t1.to_string()))
-                .add_match(result);
-            h.entry(t2)
-                .or_insert(Team::new(t2.to_string()))
-                .add_match(result.reverse());
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| Match::parse(line).map_err(|e| (n + 1, e)))
+        .partition(Result::is_ok);
+    if !errors.is_empty() {
+        return Err(ParseError {
+            lines: errors.into_iter().filter_map(Result::err).collect(),
+        });
+    }
+    let matches = matches.into_iter().flatten().collect::<Vec<_>>();
+    Ok(tally_matches(&matches, rules))
+}
+
+fn tally_matches(matches: &[Match], rules: &Rules) -> Standings {
+    let teams = matches
+        .iter()
+        .fold(HashMap::new(), |mut h, m| {
+            h.entry(m.home)
+                .or_insert(Team::new(m.home.to_string()))
+                .add_match(m.result, m.goals, rules);
+            h.entry(m.away)
+                .or_insert(Team::new(m.away.to_string()))
+                .add_match(m.result.reverse(), m.goals.map(|(h, a)| (a, h)), rules);
             h
         })
         .into_values()
         .collect::<Vec<_>>();
 
-    teams.sort_by(|a, b| b.score().cmp(&a.score()).then_with(|| a.name.cmp(&b.name)));
+    Standings {
+        teams: rank(teams, matches, rules),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub home: String,
+    pub away: String,
+}
 
-    vec![String::from(HEADER)]
-        .into_iter()
-        .chain(teams.into_iter().map(|t| t.into()))
-        .collect::<Vec<_>>()
-        .join("\n")
+impl Fixture {
+    fn new(home: &str, away: &str) -> Self {
+        Self {
+            home: home.to_string(),
+            away: away.to_string(),
+        }
+    }
+
+    pub fn with_result(self, result: MatchResult) -> Played {
+        Played {
+            fixture: self,
+            result,
+            goals: None,
+        }
+    }
+
+    pub fn with_score(self, home: usize, away: usize) -> Played {
+        Played {
+            fixture: self,
+            result: MatchResult::from_goals(home, away),
+            goals: Some((home, away)),
+        }
+    }
+}
+
+/// A fixture together with its result from the home side's point of view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Played {
+    pub fixture: Fixture,
+    pub result: MatchResult,
+    pub goals: Option<(usize, usize)>,
+}
+
+impl Played {
+    fn winner(&self) -> Option<&str> {
+        match self.result {
+            MatchResult::WIN => Some(&self.fixture.home),
+            MatchResult::LOSS => Some(&self.fixture.away),
+            MatchResult::DRAW => None,
+        }
+    }
+}
+
+pub fn standings_from(played: &[Played], rules: &Rules) -> Standings {
+    let matches = played
+        .iter()
+        .map(|p| Match {
+            home: &p.fixture.home,
+            away: &p.fixture.away,
+            result: p.result,
+            goals: p.goals,
+        })
+        .collect::<Vec<_>>();
+    tally_matches(&matches, rules)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub fixtures: Vec<Fixture>,
+    pub bye: Option<String>,
+}
+
+/// Schedules every team against every other with the circle method: the first
+/// team stays put while the rest rotate one place per round. An odd field gets
+/// a phantom opponent whose partner sits the round out. With `double` the whole
+/// schedule is repeated with home and away swapped.
+pub fn round_robin(teams: &[&str], double: bool) -> Vec<Round> {
+    let mut slots = teams.iter().copied().map(Some).collect::<Vec<_>>();
+    if slots.len() % 2 == 1 {
+        slots.push(None);
+    }
+    let n = slots.len();
+
+    let mut rounds = (0..n.saturating_sub(1))
+        .map(|r| {
+            let mut round = Round {
+                fixtures: vec![],
+                bye: None,
+            };
+            for i in 0..n / 2 {
+                let (mut home, mut away) = (slots[i], slots[n - 1 - i]);
+                if (i == 0 && r % 2 == 1) || (i > 0 && i % 2 == 1) {
+                    (home, away) = (away, home);
+                }
+                match (home, away) {
+                    (Some(home), Some(away)) => round.fixtures.push(Fixture::new(home, away)),
+                    (Some(team), None) | (None, Some(team)) => round.bye = Some(team.to_string()),
+                    (None, None) => {}
+                }
+            }
+            slots[1..].rotate_right(1);
+            round
+        })
+        .collect::<Vec<_>>();
+
+    if double {
+        let returns = rounds
+            .iter()
+            .map(|round| Round {
+                fixtures: round
+                    .fixtures
+                    .iter()
+                    .map(|f| Fixture::new(&f.away, &f.home))
+                    .collect(),
+                bye: round.bye.clone(),
+            })
+            .collect::<Vec<_>>();
+        rounds.extend(returns);
+    }
+    rounds
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BracketError {
+    UnknownFixture,
+    Draw,
+}
+
+/// A single-elimination bracket. `rounds[0]` holds the seeded slots, each later
+/// round the winners of the pairs before it; `None` is a bye or an unplayed match.
+pub struct Bracket {
+    rounds: Vec<Vec<Option<String>>>,
+}
+
+impl Bracket {
+    /// Places `teams` (best seed first) so that seeds 1 and 2 can only meet in
+    /// the final, padding the field with byes for the top seeds.
+    pub fn seeded(teams: &[&str]) -> Self {
+        let size = teams.len().max(2).next_power_of_two();
+        let mut order = vec![1];
+        while order.len() < size {
+            let len = order.len() * 2;
+            order = order.iter().flat_map(|&s| [s, len + 1 - s]).collect();
+        }
+
+        let mut rounds = vec![
+            order
+                .into_iter()
+                .map(|seed| teams.get(seed - 1).map(|t| t.to_string()))
+                .collect::<Vec<_>>(),
+        ];
+        while rounds.last().unwrap().len() > 1 {
+            let next = rounds.last().unwrap().len() / 2;
+            rounds.push(vec![None; next]);
+        }
+
+        let mut bracket = Self { rounds };
+        for i in 0..size / 2 {
+            if let (Some(team), None) | (None, Some(team)) =
+                (&bracket.rounds[0][2 * i], &bracket.rounds[0][2 * i + 1])
+            {
+                bracket.rounds[1][i] = Some(team.clone());
+            }
+        }
+        bracket
+    }
+
+    pub fn first_round(&self) -> Vec<(Option<&str>, Option<&str>)> {
+        self.rounds[0]
+            .chunks(2)
+            .map(|pair| (pair[0].as_deref(), pair[1].as_deref()))
+            .collect()
+    }
+
+    /// Matches whose participants are known but whose winner is not.
+    pub fn pending(&self) -> Vec<Fixture> {
+        self.rounds
+            .windows(2)
+            .flat_map(|w| {
+                w[0].chunks(2)
+                    .zip(&w[1])
+                    .filter_map(|(pair, winner)| match (pair, winner) {
+                        ([Some(home), Some(away)], None) => Some(Fixture::new(home, away)),
+                        _ => None,
+                    })
+            })
+            .collect()
+    }
+
+    pub fn record(&mut self, played: &Played) -> Result<(), BracketError> {
+        let winner = played.winner().ok_or(BracketError::Draw)?.to_string();
+        let Fixture { home, away } = &played.fixture;
+        for k in 0..self.rounds.len() - 1 {
+            for i in 0..self.rounds[k + 1].len() {
+                let pair = (&self.rounds[k][2 * i], &self.rounds[k][2 * i + 1]);
+                let matches = match pair {
+                    (Some(a), Some(b)) => (a == home && b == away) || (a == away && b == home),
+                    _ => false,
+                };
+                if matches && self.rounds[k + 1][i].is_none() {
+                    self.rounds[k + 1][i] = Some(winner);
+                    return Ok(());
+                }
+            }
+        }
+        Err(BracketError::UnknownFixture)
+    }
+
+    pub fn champion(&self) -> Option<&str> {
+        self.rounds.last().and_then(|r| r[0].as_deref())
+    }
 }
 
+const USAGE: &str = "usage: tournament [--format text|csv|json|markdown] [FILE]";
+
 fn main() {
-    let input: &[&str] = &[
-        "Allegoric Alaskans;Blithering Badgers;win",
-        "Allegoric Alaskans;Blithering Badgers;win",
-    ];
-    let input = input.join("\n");
-    println!("{}", tally(&input));
+    let mut format = Format::Text;
+    let mut path = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-f" | "--format" => match args.next().map(|f| f.parse()) {
+                Some(Ok(f)) => format = f,
+                Some(Err(e)) => exit_with(&e),
+                None => exit_with(USAGE),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => exit_with(USAGE),
+        }
+    }
+
+    match run(format, path) {
+        Ok(output) => println!("{output}"),
+        Err(e) => exit_with(&e.to_string()),
+    }
+}
+
+fn run(format: Format, path: Option<String>) -> Result<String, Box<dyn Error>> {
+    let input = match path {
+        Some(path) => fs::read_to_string(path)?,
+        None => io::read_to_string(io::stdin())?,
+    };
+    Ok(standings(&input, &Rules::default())?.render(format))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1)
 }
 
 #[cfg(test)]
@@ -310,4 +891,322 @@ mod test {
         .join("\n");
         assert_eq!(output, expected);
     }
+    #[test]
+    fn scorelines_are_accepted() {
+        let input: &[&str] = &[
+            "Allegoric Alaskans;Blithering Badgers;2-1",
+            "Blithering Badgers;Courageous Californians;0-0",
+        ];
+        let input = input.join("\n");
+        let output = tally(&input);
+        let expected = [
+            "Team                           | MP |  W |  D |  L |  P",
+            "Allegoric Alaskans             |  1 |  1 |  0 |  0 |  3",
+            "Blithering Badgers             |  2 |  0 |  1 |  1 |  1",
+            "Courageous Californians        |  1 |  0 |  1 |  0 |  1",
+        ]
+        .join("\n");
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn custom_points_per_result() {
+        let input: &[&str] = &[
+            "Allegoric Alaskans;Blithering Badgers;win",
+            "Courageous Californians;Devastating Donkeys;draw",
+        ];
+        let input = input.join("\n");
+        let rules = Rules {
+            win: 2,
+            draw: 1,
+            loss: 0,
+            ..Rules::default()
+        };
+        let output = tally_with(&input, &rules);
+        let expected = [
+            "Team                           | MP |  W |  D |  L |  P",
+            "Allegoric Alaskans             |  1 |  1 |  0 |  0 |  2",
+            "Courageous Californians        |  1 |  0 |  1 |  0 |  1",
+            "Devastating Donkeys            |  1 |  0 |  1 |  0 |  1",
+            "Blithering Badgers             |  1 |  0 |  0 |  1 |  0",
+        ]
+        .join("\n");
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn ties_broken_by_goal_difference_then_goals_scored() {
+        let input: &[&str] = &[
+            "Allegoric Alaskans;Devastating Donkeys;1-0",
+            "Blithering Badgers;Devastating Donkeys;3-1",
+            "Courageous Californians;Devastating Donkeys;2-0",
+        ];
+        let input = input.join("\n");
+        let rules = Rules {
+            tie_breakers: vec![TieBreaker::GoalDifference, TieBreaker::GoalsScored],
+            ..Rules::default()
+        };
+        let output = tally_with(&input, &rules);
+        let expected = [
+            "Team                           | MP |  W |  D |  L |  P",
+            "Blithering Badgers             |  1 |  1 |  0 |  0 |  3",
+            "Courageous Californians        |  1 |  1 |  0 |  0 |  3",
+            "Allegoric Alaskans             |  1 |  1 |  0 |  0 |  3",
+            "Devastating Donkeys            |  3 |  0 |  0 |  3 |  0",
+        ]
+        .join("\n");
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn ties_broken_by_head_to_head() {
+        let input: &[&str] = &[
+            "Blithering Badgers;Allegoric Alaskans;win",
+            "Allegoric Alaskans;Courageous Californians;win",
+            "Courageous Californians;Blithering Badgers;win",
+            "Courageous Californians;Devastating Donkeys;win",
+            "Blithering Badgers;Devastating Donkeys;draw",
+            "Allegoric Alaskans;Devastating Donkeys;draw",
+        ];
+        let input = input.join("\n");
+        let rules = Rules {
+            tie_breakers: vec![TieBreaker::HeadToHead],
+            ..Rules::default()
+        };
+        let output = tally_with(&input, &rules);
+        let expected = [
+            "Team                           | MP |  W |  D |  L |  P",
+            "Courageous Californians        |  3 |  2 |  0 |  1 |  6",
+            "Blithering Badgers             |  3 |  1 |  1 |  1 |  4",
+            "Allegoric Alaskans             |  3 |  1 |  1 |  1 |  4",
+            "Devastating Donkeys            |  3 |  0 |  2 |  1 |  2",
+        ]
+        .join("\n");
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn head_to_head_is_a_mini_league_of_tied_teams() {
+        let input: &[&str] = &[
+            "Blithering Badgers;Allegoric Alaskans;win",
+            "Allegoric Alaskans;Courageous Californians;win",
+            "Courageous Californians;Blithering Badgers;draw",
+            "Allegoric Alaskans;Devastating Donkeys;draw",
+            "Blithering Badgers;Devastating Donkeys;loss",
+        ];
+        let input = input.join("\n");
+        let rules = Rules {
+            tie_breakers: vec![TieBreaker::HeadToHead],
+            ..Rules::default()
+        };
+        let output = tally_with(&input, &rules);
+        let expected = [
+            "Team                           | MP |  W |  D |  L |  P",
+            "Devastating Donkeys            |  2 |  1 |  1 |  0 |  4",
+            "Blithering Badgers             |  3 |  1 |  1 |  1 |  4",
+            "Allegoric Alaskans             |  3 |  1 |  1 |  1 |  4",
+            "Courageous Californians        |  2 |  0 |  1 |  1 |  1",
+        ]
+        .join("\n");
+        assert_eq!(output, expected);
+    }
+    #[test]
+    fn try_tally_accepts_valid_input() {
+        let input = "Allegoric Alaskans;Blithering Badgers;win";
+        assert_eq!(try_tally(input), Ok(tally(input)));
+    }
+    #[test]
+    fn try_tally_skips_blank_lines() {
+        let input = "Allegoric Alaskans;Blithering Badgers;win\n\n";
+        assert_eq!(
+            try_tally(input),
+            Ok(tally("Allegoric Alaskans;Blithering Badgers;win"))
+        );
+    }
+    #[test]
+    fn parse_errors_convert_into_boxed_errors() {
+        fn boxed(input: &str) -> Result<String, Box<dyn Error>> {
+            Ok(try_tally(input)?)
+        }
+        let error = boxed("A;B;win\nA;B;dunno\nA;A;draw").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: unknown result `dunno`\nline 3: `A` cannot play itself"
+        );
+        let source = error.source().unwrap();
+        assert_eq!(source.to_string(), "unknown result `dunno`");
+    }
+    #[test]
+    fn try_tally_reports_every_bad_line() {
+        let input: &[&str] = &[
+            "Allegoric Alaskans;Blithering Badgers;win",
+            "Allegoric Alaskans;Blithering Badgers",
+            "Allegoric Alaskans;Blithering Badgers;dunno",
+            "Allegoric Alaskans;Allegoric Alaskans;draw",
+            ";Blithering Badgers;loss",
+        ];
+        let input = input.join("\n");
+        let expected = ParseError {
+            lines: vec![
+                (2, LineError::MissingFields(2)),
+                (3, LineError::UnknownResult("dunno".to_string())),
+                (4, LineError::SelfMatch("Allegoric Alaskans".to_string())),
+                (5, LineError::EmptyTeamName),
+            ],
+        };
+        assert_eq!(try_tally(&input), Err(expected));
+    }
+    #[test]
+    fn parse_error_lists_line_numbers() {
+        let err = try_tally("A;B\nA;B;2-x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1: expected 3 fields, found 2\nline 2: unknown result `2-x`"
+        );
+    }
+    fn sample() -> Standings {
+        let input: &[&str] = &[
+            "Allegoric Alaskans;Blithering Badgers;win",
+            "Blithering Badgers;Courageous, \"Californians\";draw",
+        ];
+        standings(&input.join("\n"), &Rules::default()).unwrap()
+    }
+    #[test]
+    fn text_name_column_grows_for_long_names() {
+        let input = "The Extraordinarily Long-Named Athletic Club;Blithering Badgers;win";
+        let expected = [
+            "Team                                         | MP |  W |  D |  L |  P",
+            "The Extraordinarily Long-Named Athletic Club |  1 |  1 |  0 |  0 |  3",
+            "Blithering Badgers                           |  1 |  0 |  0 |  1 |  0",
+        ]
+        .join("\n");
+        assert_eq!(tally(input), expected);
+    }
+    #[test]
+    fn csv_output_quotes_names() {
+        let expected = [
+            "Team,MP,W,D,L,P",
+            "Allegoric Alaskans,1,1,0,0,3",
+            "Blithering Badgers,2,0,1,1,1",
+            "\"Courageous, \"\"Californians\"\"\",1,0,1,0,1",
+        ]
+        .join("\n");
+        assert_eq!(sample().render(Format::Csv), expected);
+    }
+    #[test]
+    fn json_output() {
+        let expected = concat!(
+            "[{\"team\":\"Allegoric Alaskans\",\"played\":1,\"won\":1,\"drawn\":0,\"lost\":0,\"points\":3},",
+            "{\"team\":\"Blithering Badgers\",\"played\":2,\"won\":0,\"drawn\":1,\"lost\":1,\"points\":1},",
+            "{\"team\":\"Courageous, \\\"Californians\\\"\",\"played\":1,\"won\":0,\"drawn\":1,\"lost\":0,\"points\":1}]"
+        );
+        assert_eq!(sample().render(Format::Json), expected);
+    }
+    #[test]
+    fn markdown_output() {
+        let expected = [
+            "| Team | MP | W | D | L | P |",
+            "| --- | ---: | ---: | ---: | ---: | ---: |",
+            "| Allegoric Alaskans | 1 | 1 | 0 | 0 | 3 |",
+            "| Blithering Badgers | 2 | 0 | 1 | 1 | 1 |",
+            "| Courageous, \"Californians\" | 1 | 0 | 1 | 0 | 1 |",
+        ]
+        .join("\n");
+        assert_eq!(sample().render(Format::Markdown), expected);
+    }
+    #[test]
+    fn format_names_parse() {
+        assert_eq!("CSV".parse(), Ok(Format::Csv));
+        assert_eq!("md".parse(), Ok(Format::Markdown));
+        assert!("xml".parse::<Format>().is_err());
+    }
+    #[test]
+    fn round_robin_pairs_every_team_once() {
+        let teams = ["A", "B", "C", "D"];
+        let rounds = round_robin(&teams, false);
+        assert_eq!(rounds.len(), 3);
+        let mut pairs = rounds
+            .iter()
+            .flat_map(|r| r.fixtures.iter())
+            .map(|f| {
+                let mut pair = [f.home.clone(), f.away.clone()];
+                pair.sort();
+                pair
+            })
+            .collect::<Vec<_>>();
+        pairs.sort();
+        pairs.dedup();
+        assert_eq!(pairs.len(), 6);
+        for round in &rounds {
+            assert_eq!(round.fixtures.len(), 2);
+            assert_eq!(round.bye, None);
+        }
+    }
+    #[test]
+    fn round_robin_gives_byes_for_odd_team_counts() {
+        let rounds = round_robin(&["A", "B", "C", "D", "E"], false);
+        assert_eq!(rounds.len(), 5);
+        let mut byes = rounds
+            .iter()
+            .filter_map(|r| r.bye.clone())
+            .collect::<Vec<_>>();
+        byes.sort();
+        assert_eq!(byes, ["A", "B", "C", "D", "E"]);
+        assert!(rounds.iter().all(|r| r.fixtures.len() == 2));
+    }
+    #[test]
+    fn double_round_robin_swaps_home_and_away() {
+        let rounds = round_robin(&["A", "B", "C", "D"], true);
+        assert_eq!(rounds.len(), 6);
+        for (first, second) in rounds[..3].iter().zip(&rounds[3..]) {
+            for (f, g) in first.fixtures.iter().zip(&second.fixtures) {
+                assert_eq!((&f.home, &f.away), (&g.away, &g.home));
+            }
+        }
+    }
+    #[test]
+    fn played_fixtures_feed_the_standings() {
+        let played = round_robin(&["Allegoric Alaskans", "Blithering Badgers"], false)
+            .into_iter()
+            .flat_map(|r| r.fixtures)
+            .map(|f| f.with_score(0, 2))
+            .collect::<Vec<_>>();
+        let expected = [
+            "Team                           | MP |  W |  D |  L |  P",
+            "Blithering Badgers             |  1 |  1 |  0 |  0 |  3",
+            "Allegoric Alaskans             |  1 |  0 |  0 |  1 |  0",
+        ]
+        .join("\n");
+        let standings = standings_from(&played, &Rules::default());
+        assert_eq!(standings.render(Format::Text), expected);
+    }
+    #[test]
+    fn bracket_is_seeded_with_byes_for_top_seeds() {
+        let bracket = Bracket::seeded(&["S1", "S2", "S3", "S4", "S5", "S6"]);
+        assert_eq!(
+            bracket.first_round(),
+            [
+                (Some("S1"), None),
+                (Some("S4"), Some("S5")),
+                (Some("S2"), None),
+                (Some("S3"), Some("S6")),
+            ]
+        );
+        assert_eq!(
+            bracket.pending(),
+            [Fixture::new("S4", "S5"), Fixture::new("S3", "S6")]
+        );
+    }
+    #[test]
+    fn bracket_advances_winners_to_a_champion() {
+        let mut bracket = Bracket::seeded(&["S1", "S2", "S3", "S4"]);
+        assert_eq!(
+            bracket.record(&Fixture::new("S1", "S4").with_result(MatchResult::DRAW)),
+            Err(BracketError::Draw)
+        );
+        assert_eq!(
+            bracket.record(&Fixture::new("S1", "S2").with_result(MatchResult::WIN)),
+            Err(BracketError::UnknownFixture)
+        );
+        while let Some(fixture) = bracket.pending().pop() {
+            bracket.record(&fixture.with_score(1, 0)).unwrap();
+        }
+        assert_eq!(bracket.champion(), Some("S1"));
+    }
 }
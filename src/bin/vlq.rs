@@ -1,8 +1,23 @@
+use std::io::{self, Read};
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     IncompleteNumber,
+    Overflow,
+    Io(io::ErrorKind),
 }
+
 pub fn to_bytes(values: &[u32]) -> Vec<u8> {
+    to_bytes_u64(&values.iter().map(|v| *v as u64).collect::<Vec<_>>())
+}
+
+pub fn from_bytes(bytes: &[u8]) -> Result<Vec<u32>, Error> {
+    Decoder::new(bytes)
+        .map(|v| v.and_then(|v| u32::try_from(v).map_err(|_| Error::Overflow)))
+        .collect()
+}
+
+pub fn to_bytes_u64(values: &[u64]) -> Vec<u8> {
     values
         .iter()
         .rev()
@@ -19,24 +34,140 @@ pub fn to_bytes(values: &[u32]) -> Vec<u8> {
         .rev()
         .collect()
 }
-pub fn from_bytes(bytes: &[u8]) -> Result<Vec<u32>, Error> {
-    bytes
-        .chunk_by(|a, _| (*a & 1 << 7) != 0)
-        .map(|v| {
-            if v.last().unwrap() & 1 << 7 != 0 {
-                return Err(Error::IncompleteNumber);
+
+pub fn from_bytes_u64(bytes: &[u8]) -> Result<Vec<u64>, Error> {
+    Decoder::new(bytes).collect()
+}
+
+/// Maps signed integers onto unsigned ones so that small magnitudes stay
+/// small: 0, -1, 1, -2, 2 become 0, 1, 2, 3, 4.
+pub fn zigzag_encode(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+pub fn zigzag_decode(n: u64) -> i64 {
+    (n >> 1) as i64 ^ -((n & 1) as i64)
+}
+
+pub fn to_bytes_signed(values: &[i64]) -> Vec<u8> {
+    to_bytes_u64(&values.iter().map(|v| zigzag_encode(*v)).collect::<Vec<_>>())
+}
+
+pub fn from_bytes_signed(bytes: &[u8]) -> Result<Vec<i64>, Error> {
+    Decoder::new(bytes).map(|v| v.map(zigzag_decode)).collect()
+}
+
+/// LEB128, the little-endian varint used by protobuf: the least significant
+/// group of seven bits comes first.
+pub fn to_leb128_bytes(values: &[u64]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|&v| {
+            let mut res = vec![];
+            let mut n = v;
+            loop {
+                let group = n as u8 & 0x7f;
+                n >>= 7;
+                if n == 0 {
+                    res.push(group);
+                    break res;
+                }
+                res.push(group | 1 << 7);
             }
-            Ok(v.iter()
-                .fold((0, (v.len() - 1) as u32), |(sum, idx), curr| {
-                    (
-                        sum + (((*curr | 1 << 7) ^ 1 << 7) as u32) * 2_u32.pow(7 * idx),
-                        idx - idx.min(1),
-                    )
-                })
-                .0)
         })
         .collect()
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    /// Most significant group first, as in MIDI files and `to_bytes`.
+    BigEndian,
+    /// Least significant group first, as in protobuf (LEB128).
+    LittleEndian,
+}
+
+/// Reads one quantity at a time from `reader`; wrap unbuffered sources in a
+/// `BufReader`, as bytes are read individually.
+pub struct Decoder<R> {
+    reader: R,
+    order: Order,
+}
+
+impl<R: Read> Decoder<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            order: Order::BigEndian,
+        }
+    }
+
+    pub fn leb128(reader: R) -> Self {
+        Self {
+            reader,
+            order: Order::LittleEndian,
+        }
+    }
+
+    /// `Ok(None)` at the end of input; `IncompleteNumber` if it ends
+    /// mid-value.
+    pub fn next_value(&mut self) -> Result<Option<u64>, Error> {
+        let mut value: u64 = 0;
+        let mut shift = 0;
+        let mut first = true;
+        loop {
+            let Some(byte) = self.read_byte()? else {
+                return match first {
+                    true => Ok(None),
+                    false => Err(Error::IncompleteNumber),
+                };
+            };
+            first = false;
+            let group = (byte & 0x7f) as u64;
+            match self.order {
+                Order::BigEndian => {
+                    if value >> 57 != 0 {
+                        return Err(Error::Overflow);
+                    }
+                    value = value << 7 | group;
+                }
+                Order::LittleEndian => {
+                    let fits = shift < 64 && (group << shift) >> shift == group;
+                    if !fits && group != 0 {
+                        return Err(Error::Overflow);
+                    }
+                    if fits {
+                        value |= group << shift;
+                    }
+                    shift += 7;
+                }
+            }
+            if byte & 1 << 7 == 0 {
+                return Ok(Some(value));
+            }
+        }
+    }
+
+    fn read_byte(&mut self) -> Result<Option<u8>, Error> {
+        let mut byte = [0];
+        loop {
+            return match self.reader.read(&mut byte) {
+                Ok(0) => Ok(None),
+                Ok(_) => Ok(Some(byte[0])),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => Err(Error::Io(e.kind())),
+            };
+        }
+    }
+}
+
+impl<R: Read> Iterator for Decoder<R> {
+    type Item = Result<u64, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_value().transpose()
+    }
+}
+
 fn main() {
     println!("{:?}", to_bytes(&[2097152]))
 }
@@ -230,4 +361,86 @@ mod test {
         let expected = Ok(vec![8_192, 1_193_046, 268_435_455, 0, 16_383, 16_384]);
         assert_eq!(output, expected);
     }
+    #[test]
+    fn more_than_32_bits_overflows_u32() {
+        let input = &[0x90, 0x80, 0x80, 0x80, 0x00];
+        assert_eq!(from_bytes(input), Err(Error::Overflow));
+    }
+    #[test]
+    fn u64_round_trip() {
+        let values = [0, 0x7f, 0x80, u32::MAX as u64 + 1, u64::MAX];
+        assert_eq!(from_bytes_u64(&to_bytes_u64(&values)), Ok(values.to_vec()));
+    }
+    #[test]
+    fn maximum_64_bit_integer() {
+        let output = to_bytes_u64(&[u64::MAX]);
+        assert_eq!(
+            output,
+            [0x81, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]
+        );
+    }
+    #[test]
+    fn more_than_64_bits_overflows() {
+        let input = &[0x82, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00];
+        assert_eq!(from_bytes_u64(input), Err(Error::Overflow));
+    }
+    #[test]
+    fn zigzag() {
+        let pairs = [
+            (0, 0),
+            (-1, 1),
+            (1, 2),
+            (-2, 3),
+            (i64::MAX, u64::MAX - 1),
+            (i64::MIN, u64::MAX),
+        ];
+        for (signed, unsigned) in pairs {
+            assert_eq!(zigzag_encode(signed), unsigned);
+            assert_eq!(zigzag_decode(unsigned), signed);
+        }
+    }
+    #[test]
+    fn signed_round_trip() {
+        let values = [0, -1, 63, -64, 64, i64::MIN, i64::MAX];
+        assert_eq!(to_bytes_signed(&[-64]), [0x7f]);
+        assert_eq!(
+            from_bytes_signed(&to_bytes_signed(&values)),
+            Ok(values.to_vec())
+        );
+    }
+    #[test]
+    fn decoder_reads_one_value_at_a_time() {
+        let bytes = to_bytes_u64(&[0x40, 0x2000, 0x0fffffff]);
+        let mut decoder = Decoder::new(&bytes[..]);
+        assert_eq!(decoder.next_value(), Ok(Some(0x40)));
+        assert_eq!(decoder.next_value(), Ok(Some(0x2000)));
+        assert_eq!(decoder.next_value(), Ok(Some(0x0fffffff)));
+        assert_eq!(decoder.next_value(), Ok(None));
+    }
+    #[test]
+    fn decoder_reports_incomplete_trailing_value() {
+        let decoded = Decoder::new(&[0x7f, 0x81][..]).collect::<Vec<_>>();
+        assert_eq!(decoded, [Ok(0x7f), Err(Error::IncompleteNumber)]);
+    }
+    #[test]
+    fn leb128_matches_protobuf_varints() {
+        assert_eq!(
+            to_leb128_bytes(&[1, 150, 300]),
+            [0x01, 0x96, 0x01, 0xac, 0x02]
+        );
+        let decoded =
+            Decoder::leb128(&[0x01, 0x96, 0x01, 0xac, 0x02][..]).collect::<Result<Vec<_>, _>>();
+        assert_eq!(decoded, Ok(vec![1, 150, 300]));
+    }
+    #[test]
+    fn leb128_round_trip_and_overflow() {
+        let values = [0, u32::MAX as u64, u64::MAX];
+        let decoded = Decoder::leb128(&to_leb128_bytes(&values)[..]).collect::<Result<Vec<_>, _>>();
+        assert_eq!(decoded, Ok(values.to_vec()));
+        let too_big = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+        assert_eq!(
+            Decoder::leb128(&too_big[..]).next(),
+            Some(Err(Error::Overflow))
+        );
+    }
 }
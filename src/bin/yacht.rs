@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Category {
     Ones,
     Twos,
@@ -14,12 +14,32 @@ pub enum Category {
     BigStraight,
     Choice,
     Yacht,
+    ThreeOfAKind,
+    SmallStraight,
+    LargeStraight,
+}
+
+const UPPER: [Category; 6] = [
+    Category::Ones,
+    Category::Twos,
+    Category::Threes,
+    Category::Fours,
+    Category::Fives,
+    Category::Sixes,
+];
+
+impl Category {
+    fn face(&self) -> Option<u8> {
+        UPPER.iter().position(|c| c == self).map(|i| i as u8 + 1)
+    }
 }
 
 type Dice = [u8; 5];
 
 trait Utils {
     fn chunk_same(&self) -> Vec<Vec<u8>>;
+    fn most_of_a_kind(&self) -> usize;
+    fn has_run(&self, len: u8) -> bool;
 }
 
 impl Utils for Dice {
@@ -30,6 +50,14 @@ impl Utils for Dice {
         }
         h.into_values().collect()
     }
+
+    fn most_of_a_kind(&self) -> usize {
+        self.chunk_same().iter().map(|c| c.len()).max().unwrap_or(0)
+    }
+
+    fn has_run(&self, len: u8) -> bool {
+        (1..=7 - len).any(|start| (start..start + len).all(|n| self.contains(&n)))
+    }
 }
 
 pub fn score(mut dice: Dice, category: Category) -> u8 {
@@ -75,8 +103,191 @@ pub fn score(mut dice: Dice, category: Category) -> u8 {
             5 => 50,
             _ => 0,
         },
+        Category::ThreeOfAKind | Category::SmallStraight | Category::LargeStraight => 0,
+    }
+}
+
+pub trait Ruleset {
+    fn categories(&self) -> &'static [Category];
+    fn score(&self, dice: Dice, category: Category) -> u8;
+
+    fn upper_bonus(&self, _upper_total: u16) -> u16 {
+        0
+    }
+
+    /// Extra points for every further five-of-a-kind once the `Yacht` box
+    /// holds a non-zero score; zero disables bonuses and joker rules.
+    fn yacht_bonus(&self) -> u16 {
+        0
+    }
+}
+
+pub struct Yacht;
+
+impl Ruleset for Yacht {
+    fn categories(&self) -> &'static [Category] {
+        &[
+            Category::Ones,
+            Category::Twos,
+            Category::Threes,
+            Category::Fours,
+            Category::Fives,
+            Category::Sixes,
+            Category::FullHouse,
+            Category::FourOfAKind,
+            Category::LittleStraight,
+            Category::BigStraight,
+            Category::Choice,
+            Category::Yacht,
+        ]
+    }
+
+    fn score(&self, dice: Dice, category: Category) -> u8 {
+        score(dice, category)
+    }
+}
+
+/// Yahtzee scoring, with `Category::Yacht` as the Yahtzee box and
+/// `Category::Choice` as Chance.
+pub struct Yahtzee;
+
+impl Ruleset for Yahtzee {
+    fn categories(&self) -> &'static [Category] {
+        &[
+            Category::Ones,
+            Category::Twos,
+            Category::Threes,
+            Category::Fours,
+            Category::Fives,
+            Category::Sixes,
+            Category::ThreeOfAKind,
+            Category::FourOfAKind,
+            Category::FullHouse,
+            Category::SmallStraight,
+            Category::LargeStraight,
+            Category::Yacht,
+            Category::Choice,
+        ]
+    }
+
+    fn score(&self, dice: Dice, category: Category) -> u8 {
+        let sum = dice.iter().sum();
+        match category {
+            Category::ThreeOfAKind if dice.most_of_a_kind() >= 3 => sum,
+            Category::FourOfAKind if dice.most_of_a_kind() >= 4 => sum,
+            Category::FullHouse => match score(dice, Category::FullHouse) {
+                0 => 0,
+                _ => 25,
+            },
+            Category::SmallStraight if dice.has_run(4) => 30,
+            Category::LargeStraight if dice.has_run(5) => 40,
+            Category::ThreeOfAKind
+            | Category::FourOfAKind
+            | Category::SmallStraight
+            | Category::LargeStraight
+            | Category::LittleStraight
+            | Category::BigStraight => 0,
+            _ => score(dice, category),
+        }
+    }
+
+    fn upper_bonus(&self, upper_total: u16) -> u16 {
+        if upper_total >= 63 { 35 } else { 0 }
+    }
+
+    fn yacht_bonus(&self) -> u16 {
+        100
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ScorecardError {
+    UnknownCategory,
+    AlreadyFilled,
+    JokerMustUseUpper(Category),
+    /// A die showing something other than 1 to 6.
+    InvalidDie(u8),
+}
+
+pub struct Scorecard<R: Ruleset> {
+    rules: R,
+    boxes: HashMap<Category, u8>,
+    bonus: u16,
+}
+
+impl<R: Ruleset> Scorecard<R> {
+    pub fn new(rules: R) -> Self {
+        Self {
+            rules,
+            boxes: HashMap::new(),
+            bonus: 0,
+        }
+    }
+
+    pub fn fill(&mut self, dice: Dice, category: Category) -> Result<u8, ScorecardError> {
+        if let Some(&die) = dice.iter().find(|d| !(1..=6).contains(*d)) {
+            return Err(ScorecardError::InvalidDie(die));
+        }
+        if !self.rules.categories().contains(&category) {
+            return Err(ScorecardError::UnknownCategory);
+        }
+        if self.boxes.contains_key(&category) {
+            return Err(ScorecardError::AlreadyFilled);
+        }
+
+        let mut points = self.rules.score(dice, category);
+        let joker = self.rules.yacht_bonus() > 0
+            && dice.most_of_a_kind() == 5
+            && self.boxes.contains_key(&Category::Yacht);
+        if joker {
+            let upper = UPPER[dice[0] as usize - 1];
+            if upper != category && !self.boxes.contains_key(&upper) {
+                return Err(ScorecardError::JokerMustUseUpper(upper));
+            }
+            if self.boxes[&Category::Yacht] > 0 {
+                self.bonus += self.rules.yacht_bonus();
+            }
+            points = match category {
+                Category::FullHouse => 25,
+                Category::SmallStraight => 30,
+                Category::LargeStraight => 40,
+                _ => points,
+            };
+        }
+
+        self.boxes.insert(category, points);
+        Ok(points)
+    }
+
+    pub fn get(&self, category: Category) -> Option<u8> {
+        self.boxes.get(&category).copied()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.boxes.len() == self.rules.categories().len()
+    }
+
+    pub fn upper_total(&self) -> u16 {
+        self.boxes
+            .iter()
+            .filter(|(c, _)| c.face().is_some())
+            .map(|(_, p)| *p as u16)
+            .sum()
+    }
+
+    pub fn upper_bonus(&self) -> u16 {
+        self.rules.upper_bonus(self.upper_total())
+    }
+
+    pub fn yacht_bonus(&self) -> u16 {
+        self.bonus
+    }
+
+    pub fn total(&self) -> u16 {
+        self.boxes.values().map(|p| *p as u16).sum::<u16>() + self.upper_bonus() + self.bonus
     }
 }
+
 fn main() {}
 
 #[cfg(test)]
@@ -227,4 +438,108 @@ mod test {
         let expected = 10;
         assert_eq!(score([2, 2, 2, 2, 2], Category::Choice), expected);
     }
+    #[test]
+    fn yahtzee_three_of_a_kind_sums_all_dice() {
+        assert_eq!(Yahtzee.score([4, 4, 4, 2, 6], Category::ThreeOfAKind), 20);
+        assert_eq!(Yahtzee.score([4, 4, 3, 2, 6], Category::ThreeOfAKind), 0);
+    }
+    #[test]
+    fn yahtzee_four_of_a_kind_sums_all_dice() {
+        assert_eq!(Yahtzee.score([6, 6, 4, 6, 6], Category::FourOfAKind), 28);
+    }
+    #[test]
+    fn yahtzee_full_house_is_fixed() {
+        assert_eq!(Yahtzee.score([2, 2, 4, 4, 4], Category::FullHouse), 25);
+    }
+    #[test]
+    fn yahtzee_small_straight_is_a_run_of_four() {
+        assert_eq!(Yahtzee.score([1, 3, 4, 5, 6], Category::SmallStraight), 30);
+        assert_eq!(Yahtzee.score([1, 2, 3, 5, 6], Category::SmallStraight), 0);
+    }
+    #[test]
+    fn yahtzee_large_straight_accepts_both_runs() {
+        assert_eq!(Yahtzee.score([1, 2, 3, 4, 5], Category::LargeStraight), 40);
+        assert_eq!(Yahtzee.score([6, 5, 4, 3, 2], Category::LargeStraight), 40);
+    }
+    #[test]
+    fn yacht_scorecard_rejects_yahtzee_categories() {
+        let mut card = Scorecard::new(Yacht);
+        assert_eq!(
+            card.fill([1, 2, 3, 4, 5], Category::SmallStraight),
+            Err(ScorecardError::UnknownCategory)
+        );
+    }
+    #[test]
+    fn scorecard_box_can_only_be_filled_once() {
+        let mut card = Scorecard::new(Yacht);
+        assert_eq!(card.fill([1, 1, 1, 3, 5], Category::Ones), Ok(3));
+        assert_eq!(
+            card.fill([1, 1, 3, 3, 5], Category::Ones),
+            Err(ScorecardError::AlreadyFilled)
+        );
+    }
+    #[test]
+    fn yahtzee_upper_bonus_at_63() {
+        let mut card = Scorecard::new(Yahtzee);
+        for (face, category) in UPPER.into_iter().enumerate() {
+            let n = face as u8 + 1;
+            card.fill([n, n, n, 1, 1], category).unwrap();
+        }
+        assert_eq!(card.upper_total(), 65);
+        assert_eq!(card.upper_bonus(), 35);
+        assert_eq!(card.total(), 100);
+    }
+    #[test]
+    fn yacht_has_no_upper_bonus() {
+        let mut card = Scorecard::new(Yacht);
+        for (face, category) in UPPER.into_iter().enumerate() {
+            let n = face as u8 + 1;
+            card.fill([n, n, n, n, 1], category).unwrap();
+        }
+        assert_eq!(card.upper_bonus(), 0);
+    }
+    #[test]
+    fn yahtzee_bonus_and_joker() {
+        let mut card = Scorecard::new(Yahtzee);
+        assert_eq!(card.fill([3, 3, 3, 3, 3], Category::Yacht), Ok(50));
+        assert_eq!(
+            card.fill([4, 4, 4, 4, 4], Category::LargeStraight),
+            Err(ScorecardError::JokerMustUseUpper(Category::Fours))
+        );
+        assert_eq!(card.fill([4, 4, 4, 4, 4], Category::Fours), Ok(20));
+        assert_eq!(card.fill([4, 4, 4, 4, 4], Category::LargeStraight), Ok(40));
+        assert_eq!(card.yacht_bonus(), 200);
+        assert_eq!(card.total(), 310);
+    }
+    #[test]
+    fn no_yahtzee_bonus_after_scratching() {
+        let mut card = Scorecard::new(Yahtzee);
+        assert_eq!(card.fill([1, 2, 3, 3, 3], Category::Yacht), Ok(0));
+        assert_eq!(card.fill([2, 2, 2, 2, 2], Category::Twos), Ok(10));
+        assert_eq!(card.fill([5, 5, 5, 5, 5], Category::Fives), Ok(25));
+        assert_eq!(card.fill([6, 6, 6, 6, 6], Category::Sixes), Ok(30));
+        assert_eq!(card.fill([1, 1, 1, 1, 1], Category::Ones), Ok(5));
+        assert_eq!(card.fill([1, 1, 1, 1, 1], Category::FullHouse), Ok(25));
+        assert_eq!(card.yacht_bonus(), 0);
+    }
+    #[test]
+    fn scorecard_rejects_invalid_dice() {
+        let mut card = Scorecard::new(Yahtzee);
+        assert_eq!(card.fill([5, 5, 5, 5, 5], Category::Yacht), Ok(50));
+        assert_eq!(
+            card.fill([0, 0, 0, 0, 0], Category::Ones),
+            Err(ScorecardError::InvalidDie(0))
+        );
+        assert_eq!(
+            card.fill([1, 2, 3, 4, 7], Category::Choice),
+            Err(ScorecardError::InvalidDie(7))
+        );
+        assert_eq!(card.get(Category::Ones), None);
+    }
+    #[test]
+    fn yacht_rules_score_yahtzee_only_categories_as_zero() {
+        assert_eq!(score([2, 2, 2, 5, 6], Category::ThreeOfAKind), 0);
+        assert_eq!(Yacht.score([1, 3, 4, 5, 6], Category::SmallStraight), 0);
+        assert_eq!(Yacht.score([1, 2, 3, 4, 5], Category::LargeStraight), 0);
+    }
 }
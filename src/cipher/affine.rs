@@ -0,0 +1,149 @@
+use super::{Cipher, CipherError};
+use std::collections::HashMap;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum AffineCipherError {
+    NotCoprime(i32),
+    CharNotInAlphabet(char),
+    EmptyAlphabet,
+    DuplicateChar(char),
+}
+
+/// An ordered set of symbols; the affine map works on their positions modulo
+/// the alphabet size.
+#[derive(Debug, Clone)]
+pub struct Alphabet {
+    chars: Vec<char>,
+    index: HashMap<char, usize>,
+}
+
+impl Alphabet {
+    pub fn new(symbols: &str) -> Result<Self, AffineCipherError> {
+        let chars = symbols.chars().collect::<Vec<_>>();
+        if chars.is_empty() {
+            return Err(AffineCipherError::EmptyAlphabet);
+        }
+        let mut index = HashMap::new();
+        for (i, c) in chars.iter().enumerate() {
+            if index.insert(*c, i).is_some() {
+                return Err(AffineCipherError::DuplicateChar(*c));
+            }
+        }
+        Ok(Self { chars, index })
+    }
+
+    pub fn lowercase() -> Self {
+        Self::new("abcdefghijklmnopqrstuvwxyz").unwrap()
+    }
+
+    pub fn alphanumeric() -> Self {
+        Self::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz").unwrap()
+    }
+
+    pub fn printable_ascii() -> Self {
+        Self::new(&(' '..='~').collect::<String>()).unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    fn map_char(&self, c: char, f: impl Fn(i64) -> i64) -> Result<char, AffineCipherError> {
+        let x = *self
+            .index
+            .get(&c)
+            .ok_or(AffineCipherError::CharNotInAlphabet(c))?;
+        Ok(self.chars[f(x as i64).rem_euclid(self.len() as i64) as usize])
+    }
+
+    fn map(&self, text: &str, f: impl Fn(i64) -> i64) -> Result<String, AffineCipherError> {
+        text.chars().map(|c| self.map_char(c, &f)).collect()
+    }
+}
+
+/// Lowercases `text` and maps its letters through `alphabet`, passing digits
+/// through unchanged and dropping everything that is not ASCII alphanumeric.
+fn map_letters(
+    text: &str,
+    alphabet: &Alphabet,
+    f: impl Fn(i64) -> i64,
+) -> Result<Vec<char>, AffineCipherError> {
+    text.to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| match c.is_ascii_digit() {
+            true => Ok(c),
+            false => alphabet.map_char(c, &f),
+        })
+        .collect()
+}
+
+/// Encrypts the ASCII letters and digits of `plaintext` over `a..=z`, dropping
+/// every other character and grouping the output in fives.
+pub fn encode(plaintext: &str, a: i32, b: i32) -> Result<String, AffineCipherError> {
+    let alphabet = Alphabet::lowercase();
+    find_mmi(a.into(), alphabet.len() as i64).ok_or(AffineCipherError::NotCoprime(a))?;
+    let chars = map_letters(plaintext, &alphabet, |x| i64::from(a) * x + i64::from(b))?;
+    Ok(chars
+        .chunks(5)
+        .map(String::from_iter)
+        .collect::<Vec<String>>()
+        .join(" "))
+}
+
+/// Decrypts the output of `encode`, ignoring case and any character that is
+/// not an ASCII letter or digit.
+pub fn decode(ciphertext: &str, a: i32, b: i32) -> Result<String, AffineCipherError> {
+    let alphabet = Alphabet::lowercase();
+    let inverse =
+        find_mmi(a.into(), alphabet.len() as i64).ok_or(AffineCipherError::NotCoprime(a))?;
+    let chars = map_letters(ciphertext, &alphabet, |x| inverse * (x - i64::from(b)))?;
+    Ok(chars.into_iter().collect())
+}
+
+/// Encrypts every character of `plaintext` as `a * x + b` modulo the size of
+/// `alphabet`. Unlike `encode`, nothing is normalised, dropped or grouped.
+pub fn encode_with(
+    plaintext: &str,
+    a: i32,
+    b: i32,
+    alphabet: &Alphabet,
+) -> Result<String, AffineCipherError> {
+    let m = alphabet.len() as i64;
+    find_mmi(a.into(), m).ok_or(AffineCipherError::NotCoprime(a))?;
+    alphabet.map(plaintext, |x| i64::from(a) * x + i64::from(b))
+}
+
+pub fn decode_with(
+    ciphertext: &str,
+    a: i32,
+    b: i32,
+    alphabet: &Alphabet,
+) -> Result<String, AffineCipherError> {
+    let m = alphabet.len() as i64;
+    let inverse = find_mmi(a.into(), m).ok_or(AffineCipherError::NotCoprime(a))?;
+    alphabet.map(ciphertext, |x| inverse * (x - i64::from(b)))
+}
+
+fn find_mmi(a: i64, m: i64) -> Option<i64> {
+    (0..m).find(|n| (a * n).rem_euclid(m) == 1 % m)
+}
+
+pub struct Affine {
+    pub a: i32,
+    pub b: i32,
+}
+
+impl Cipher for Affine {
+    fn encrypt(&self, plaintext: &str) -> Result<String, CipherError> {
+        Ok(encode(plaintext, self.a, self.b)?)
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<String, CipherError> {
+        Ok(decode(ciphertext, self.a, self.b)?)
+    }
+}
@@ -0,0 +1,414 @@
+use super::affine;
+use super::rail_fence::{self, RailFence};
+use super::rotational::rotate;
+use super::simple;
+use std::collections::HashMap;
+
+/// Relative frequency (%) of each letter `a..=z` in English text.
+pub const ENGLISH_FREQUENCIES: [f64; 26] = [
+    8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153, 0.772, 4.025, 2.406,
+    6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150, 1.974, 0.074,
+];
+
+/// The most common English bigrams with their relative frequency (%).
+const ENGLISH_BIGRAMS: [(&str, f64); 42] = [
+    ("th", 3.56),
+    ("he", 3.07),
+    ("in", 2.43),
+    ("er", 2.05),
+    ("an", 1.99),
+    ("re", 1.85),
+    ("on", 1.76),
+    ("at", 1.49),
+    ("en", 1.45),
+    ("nd", 1.35),
+    ("ti", 1.34),
+    ("es", 1.34),
+    ("or", 1.28),
+    ("te", 1.20),
+    ("of", 1.17),
+    ("ed", 1.17),
+    ("is", 1.13),
+    ("it", 1.12),
+    ("al", 1.09),
+    ("ar", 1.07),
+    ("st", 1.05),
+    ("to", 1.04),
+    ("nt", 1.04),
+    ("ng", 0.95),
+    ("se", 0.93),
+    ("ha", 0.93),
+    ("as", 0.87),
+    ("ou", 0.87),
+    ("io", 0.83),
+    ("le", 0.83),
+    ("ve", 0.83),
+    ("co", 0.79),
+    ("me", 0.79),
+    ("de", 0.76),
+    ("hi", 0.76),
+    ("ri", 0.73),
+    ("ro", 0.73),
+    ("ic", 0.70),
+    ("ne", 0.69),
+    ("ea", 0.69),
+    ("ra", 0.69),
+    ("ce", 0.65),
+];
+
+/// Frequency (%) assumed for any bigram missing from `ENGLISH_BIGRAMS`.
+const RARE_BIGRAM: f64 = 0.01;
+
+/// Index of coincidence of English text; uniformly random letters give 1/26.
+pub const ENGLISH_IOC: f64 = 0.0667;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate<K> {
+    pub key: K,
+    pub plaintext: String,
+    /// Lower is more English-like, see `english_score`.
+    pub score: f64,
+}
+
+fn letters(text: &str) -> Vec<u8> {
+    text.chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_lowercase() as u8 - b'a')
+        .collect()
+}
+
+pub fn letter_counts(text: &str) -> [usize; 26] {
+    letters(text).into_iter().fold([0; 26], |mut counts, l| {
+        counts[l as usize] += 1;
+        counts
+    })
+}
+
+/// Pearson's chi-squared statistic of the letter counts of `text` against
+/// `ENGLISH_FREQUENCIES`.
+pub fn chi_squared(text: &str) -> f64 {
+    let counts = letter_counts(text);
+    let total = counts.iter().sum::<usize>() as f64;
+    counts
+        .iter()
+        .zip(ENGLISH_FREQUENCIES)
+        .map(|(&observed, freq)| {
+            let expected = total * freq / 100.0;
+            (observed as f64 - expected).powi(2) / expected
+        })
+        .sum()
+}
+
+/// Mean negative log10 probability of the letter bigrams of `text`.
+pub fn bigram_penalty(text: &str) -> f64 {
+    let letters = letters(text);
+    if letters.len() < 2 {
+        return 0.0;
+    }
+    let penalty = letters
+        .windows(2)
+        .map(|w| {
+            let freq = ENGLISH_BIGRAMS
+                .iter()
+                .find(|(b, _)| b.as_bytes() == [w[0] + b'a', w[1] + b'a'])
+                .map_or(RARE_BIGRAM, |(_, f)| *f);
+            -(freq / 100.0).log10()
+        })
+        .sum::<f64>();
+    penalty / (letters.len() - 1) as f64
+}
+
+/// Combines the per-letter chi-squared statistic with the bigram penalty;
+/// English plaintext scores low, random letters high.
+pub fn english_score(text: &str) -> f64 {
+    let len = letters(text).len();
+    if len == 0 {
+        return f64::INFINITY;
+    }
+    chi_squared(text) / len as f64 + bigram_penalty(text)
+}
+
+/// Scores every `(key, plaintext)` pair and keeps the `top` best.
+pub fn rank<K>(candidates: impl IntoIterator<Item = (K, String)>, top: usize) -> Vec<Candidate<K>> {
+    let mut ranked = candidates
+        .into_iter()
+        .map(|(key, plaintext)| Candidate {
+            score: english_score(&plaintext),
+            key,
+            plaintext,
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|a, b| a.score.total_cmp(&b.score));
+    ranked.truncate(top);
+    ranked
+}
+
+/// Tries all 26 shifts; each key is the one `rotate` was called with to encrypt.
+pub fn break_rotate(ciphertext: &str, top: usize) -> Vec<Candidate<u8>> {
+    rank(
+        (0..26).map(|key| (key, rotate(ciphertext, (26 - key) % 26))),
+        top,
+    )
+}
+
+/// Tries all 312 `(a, b)` keys. Atbash is the affine key `(25, 25)`, so
+/// Atbash ciphertext is broken here as well. Only the letters of
+/// `ciphertext` are kept, so candidates carry no spaces or punctuation.
+pub fn break_affine(ciphertext: &str, top: usize) -> Vec<Candidate<(i32, i32)>> {
+    let ciphertext = letters(ciphertext)
+        .into_iter()
+        .map(|l| (b'a' + l) as char)
+        .collect::<String>();
+    let keys = (1..26)
+        .filter(|a| gcd(*a, 26) == 1)
+        .flat_map(|a| (0..26).map(move |b| (a, b)));
+    rank(
+        keys.filter_map(|(a, b)| {
+            affine::decode(&ciphertext, a, b)
+                .ok()
+                .map(|plain| ((a, b), plain))
+        }),
+        top,
+    )
+}
+
+/// Probability that two letters drawn from `text` without replacement match.
+pub fn index_of_coincidence(text: &str) -> f64 {
+    let counts = letter_counts(text);
+    let n = counts.iter().sum::<usize>();
+    if n < 2 {
+        return 0.0;
+    }
+    let pairs = counts
+        .iter()
+        .map(|c| c * c.saturating_sub(1))
+        .sum::<usize>();
+    pairs as f64 / (n * (n - 1)) as f64
+}
+
+fn columns(letters: &[u8], len: usize) -> Vec<String> {
+    (0..len)
+        .map(|start| {
+            letters
+                .iter()
+                .skip(start)
+                .step_by(len)
+                .map(|l| char::from(l + b'a'))
+                .collect()
+        })
+        .collect()
+}
+
+/// Kasiski examination: distances between repeated trigrams are multiples of
+/// the key length. Returns `(length, votes)` for `2..=max_len`, most voted first.
+pub fn kasiski(ciphertext: &str, max_len: usize) -> Vec<(usize, usize)> {
+    let letters = letters(ciphertext);
+    let mut seen: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for (i, trigram) in letters.windows(3).enumerate() {
+        seen.entry(trigram).or_default().push(i);
+    }
+
+    let distances = seen
+        .values()
+        .flat_map(|positions| positions.windows(2).map(|w| w[1] - w[0]))
+        .collect::<Vec<_>>();
+    let mut votes = (2..=max_len)
+        .map(|len| (len, distances.iter().filter(|d| *d % len == 0).count()))
+        .collect::<Vec<_>>();
+    votes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    votes
+}
+
+/// Friedman test: the mean index of coincidence of the columns obtained by
+/// splitting the ciphertext every `len` letters, for each `len` in `1..=max_len`.
+pub fn friedman(ciphertext: &str, max_len: usize) -> Vec<(usize, f64)> {
+    let letters = letters(ciphertext);
+    (1..=max_len.min(letters.len() / 2))
+        .map(|len| {
+            let columns = columns(&letters, len);
+            let ioc = columns.iter().map(|c| index_of_coincidence(c)).sum::<f64>() / len as f64;
+            (len, ioc)
+        })
+        .collect()
+}
+
+/// Keeps the lengths whose column IoC is close to the best one (multiples of
+/// the real key length score as well as the key itself), then lets Kasiski
+/// votes pick among them, favouring the shortest.
+pub fn estimate_key_length(ciphertext: &str, max_len: usize) -> Option<usize> {
+    let iocs = friedman(ciphertext, max_len);
+    let best = iocs.iter().map(|(_, ioc)| *ioc).fold(0.0, f64::max);
+    if best == 0.0 {
+        return None;
+    }
+    let plausible = iocs
+        .iter()
+        .filter(|(_, ioc)| *ioc >= best * 0.9)
+        .map(|(len, _)| *len)
+        .collect::<Vec<_>>();
+    if plausible == [1] {
+        return Some(1);
+    }
+
+    let votes = kasiski(ciphertext, max_len);
+    let votes_for = |len: usize| votes.iter().find(|(l, _)| *l == len).map_or(0, |(_, v)| *v);
+    plausible
+        .into_iter()
+        .filter(|&len| len > 1)
+        .max_by(|&a, &b| votes_for(a).cmp(&votes_for(b)).then(b.cmp(&a)))
+}
+
+/// Solves each column as a Caesar shift by chi-squared against English.
+pub fn recover_key(ciphertext: &str, key_len: usize) -> String {
+    columns(&letters(ciphertext), key_len.max(1))
+        .iter()
+        .map(|column| {
+            let shift = (0..26u8)
+                .min_by(|&a, &b| {
+                    let a = chi_squared(&rotate(column, (26 - a) % 26));
+                    let b = chi_squared(&rotate(column, (26 - b) % 26));
+                    a.total_cmp(&b)
+                })
+                .unwrap();
+            char::from(shift + b'a')
+        })
+        .collect()
+}
+
+/// Recovers the key of `simple::encode` ciphertext, trying key lengths up to
+/// `max_len`. Needs a few dozen letters per key character to be reliable.
+pub fn break_vigenere(ciphertext: &str, max_len: usize) -> Option<Candidate<String>> {
+    let ciphertext = letters(ciphertext)
+        .into_iter()
+        .map(|l| char::from(l + b'a'))
+        .collect::<String>();
+    let key_len = estimate_key_length(&ciphertext, max_len)?;
+    let key = recover_key(&ciphertext, key_len);
+    let plaintext = simple::decode(&key, &ciphertext)?;
+    Some(Candidate {
+        score: english_score(&plaintext),
+        key,
+        plaintext,
+    })
+}
+
+/// Tries every rail count in `2..=max_rails` with every starting offset; the
+/// key is `(rails, offset)` for `RailFence::new(rails).with_offset(offset)`.
+pub fn break_rail_fence(
+    ciphertext: &str,
+    max_rails: u32,
+    top: usize,
+) -> Vec<Candidate<(u32, usize)>> {
+    let max_rails = max_rails.min(ciphertext.chars().count() as u32);
+    let keys = (2..=max_rails)
+        .flat_map(|rails| (0..rail_fence::period(rails as usize)).map(move |o| (rails, o)));
+    rank(
+        keys.map(|(rails, offset)| {
+            let plain = RailFence::new(rails).with_offset(offset).decode(ciphertext);
+            ((rails, offset), plain)
+        }),
+        top,
+    )
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cipher::atbash;
+    const PLAIN_TEXT: &str = "It was the best of times, it was the worst of times, \
+        it was the age of wisdom, it was the age of foolishness";
+    #[test]
+    fn english_scores_better_than_gibberish() {
+        assert!(english_score(PLAIN_TEXT) < english_score("qxzjv kwpzq xjjvq zzkwy"));
+    }
+    #[test]
+    fn breaks_rotation() {
+        let ciphertext = rotate(PLAIN_TEXT, 7);
+        let best = &break_rotate(&ciphertext, 3)[0];
+        assert_eq!(best.key, 7);
+        assert_eq!(best.plaintext, PLAIN_TEXT);
+    }
+    #[test]
+    fn returns_requested_number_of_candidates() {
+        let ranked = break_rotate("uryyb jbeyq", 5);
+        assert_eq!(ranked.len(), 5);
+        assert!(ranked.windows(2).all(|w| w[0].score <= w[1].score));
+    }
+    #[test]
+    fn breaks_affine() {
+        let ciphertext = affine::encode(PLAIN_TEXT, 5, 7).unwrap();
+        let ranked = break_affine(&ciphertext, 10);
+        assert_eq!(ranked[0].key, (5, 7));
+        assert!(ranked[0].plaintext.starts_with("itwasthebestoftimes"));
+    }
+    #[test]
+    fn breaks_atbash_as_affine() {
+        let ciphertext = atbash::encode(PLAIN_TEXT);
+        assert_eq!(break_affine(&ciphertext, 1)[0].key, (25, 25));
+    }
+    #[test]
+    fn breaks_punctuated_ciphertext() {
+        let ciphertext = "Vg jnf gur orfg bs gvzrf, vg jnf gur jbefg bs gvzrf!";
+        assert_eq!(break_affine(ciphertext, 1)[0].key, (1, 13));
+        let ciphertext = "Rg dzh gsv yvhg lu grnvh, rg dzh gsv dlihg lu grnvh!";
+        let best = &break_affine(ciphertext, 1)[0];
+        assert_eq!(best.key, (25, 25));
+        assert_eq!(best.plaintext, "itwasthebestoftimesitwastheworstoftimes");
+    }
+    const LONG_TEXT: &str = "It is a truth universally acknowledged, that a single man in \
+        possession of a good fortune, must be in want of a wife. However little known the \
+        feelings or views of such a man may be on his first entering a neighbourhood, this \
+        truth is so well fixed in the minds of the surrounding families, that he is \
+        considered the rightful property of some one or other of their daughters. My dear \
+        Mr Bennet, said his lady to him one day, have you heard that Netherfield Park is let \
+        at last? Mr Bennet replied that he had not. But it is, returned she; for Mrs Long \
+        has just been here, and she told me all about it.";
+    fn normalized(text: &str) -> String {
+        text.to_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_lowercase())
+            .collect()
+    }
+    #[test]
+    fn index_of_coincidence_of_english_and_uniform_text() {
+        assert!((index_of_coincidence(LONG_TEXT) - ENGLISH_IOC).abs() < 0.01);
+        let uniform = "abcdefghijklmnopqrstuvwxyz".repeat(4);
+        assert!((index_of_coincidence(&uniform) - 1.0 / 26.0).abs() < 0.01);
+    }
+    #[test]
+    fn kasiski_votes_for_the_key_length() {
+        let ciphertext = simple::encode("lemon", &normalized(LONG_TEXT)).unwrap();
+        let votes = kasiski(&ciphertext, 12);
+        let five = votes.iter().find(|(len, _)| *len == 5).unwrap().1;
+        assert!(votes.iter().all(|(len, v)| *len == 5 || *v <= five));
+    }
+    #[test]
+    fn estimates_key_length() {
+        let ciphertext = simple::encode("lemon", &normalized(LONG_TEXT)).unwrap();
+        assert_eq!(estimate_key_length(&ciphertext, 12), Some(5));
+    }
+    #[test]
+    fn breaks_vigenere() {
+        let plaintext = normalized(LONG_TEXT);
+        let ciphertext = simple::encode("austen", &plaintext).unwrap();
+        let broken = break_vigenere(&ciphertext, 12).unwrap();
+        assert_eq!(broken.key, "austen");
+        assert_eq!(broken.plaintext, plaintext);
+    }
+    #[test]
+    fn too_short_to_break() {
+        assert_eq!(break_vigenere("a", 12), None);
+    }
+    #[test]
+    fn breaks_rail_fence() {
+        let plaintext = normalized(PLAIN_TEXT);
+        let ciphertext = RailFence::new(4).with_offset(2).encode(&plaintext);
+        let best = &break_rail_fence(&ciphertext, 8, 5)[0];
+        assert_eq!(best.key, (4, 2));
+        assert_eq!(best.plaintext, plaintext);
+    }
+}
@@ -0,0 +1,49 @@
+use super::{Cipher, CipherError};
+
+pub fn encode(plain: &str) -> String {
+    plain
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| {
+            if c.is_ascii_alphabetic() {
+                match c.is_uppercase() {
+                    true => char::from(b'Z' - c as u8 + b'a'),
+                    false => char::from(b'z' - c as u8 + b'a'),
+                }
+            } else {
+                c
+            }
+        })
+        .collect::<Vec<_>>()
+        .chunks(5)
+        .map(String::from_iter)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// "Decipher" with the Atbash cipher.
+pub fn decode(cipher: &str) -> String {
+    cipher
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| {
+            if c.is_ascii_alphabetic() {
+                (b'z' - c.to_ascii_lowercase() as u8 + b'a').into()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+pub struct Atbash;
+
+impl Cipher for Atbash {
+    fn encrypt(&self, plaintext: &str) -> Result<String, CipherError> {
+        Ok(encode(plaintext))
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<String, CipherError> {
+        Ok(decode(ciphertext))
+    }
+}
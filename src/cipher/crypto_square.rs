@@ -0,0 +1,186 @@
+use super::{Cipher, CipherError};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum CryptoSquareError {
+    ZeroDimension,
+    TooSmall {
+        rows: usize,
+        columns: usize,
+        len: usize,
+    },
+    InvalidOrder,
+    InvalidCiphertext,
+}
+
+impl From<CryptoSquareError> for CipherError {
+    fn from(value: CryptoSquareError) -> Self {
+        match value {
+            CryptoSquareError::InvalidCiphertext => CipherError::InvalidCiphertext,
+            _ => CipherError::InvalidKey,
+        }
+    }
+}
+
+pub fn encrypt(input: &str) -> String {
+    CryptoSquare::new().encrypt(input).unwrap()
+}
+
+/// Reverses `encrypt`: the ciphertext is `c` space-separated chunks of `r`
+/// characters each (short columns padded with trailing spaces), read back
+/// row by row.
+pub fn decrypt(input: &str) -> Option<String> {
+    CryptoSquare::new().decrypt(input).ok()
+}
+
+/// The rectangle and column order used to transpose normalised plaintext.
+/// Unset dimensions are derived from the text length: with neither given the
+/// square is `c = ceil(sqrt(len))` columns wide, as in `encrypt`.
+#[derive(Debug, Clone, Default)]
+pub struct CryptoSquare {
+    rows: Option<usize>,
+    columns: Option<usize>,
+    drop_padding: bool,
+    order: Option<Vec<usize>>,
+}
+
+impl CryptoSquare {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rows(mut self, rows: usize) -> Self {
+        self.rows = Some(rows);
+        self
+    }
+
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Leaves short columns unpadded, so chunks differ in length.
+    pub fn without_padding(mut self) -> Self {
+        self.drop_padding = true;
+        self
+    }
+
+    /// Reads the columns in the given order: `order[k]` is the column
+    /// emitted as the `k`-th chunk. Must be a permutation of `0..columns`.
+    pub fn with_column_order(mut self, order: Vec<usize>) -> Self {
+        self.columns = Some(order.len());
+        self.order = Some(order);
+        self
+    }
+
+    /// Columnar transposition key: one column per keyword letter, read in
+    /// alphabetical order of the letters (ties left to right).
+    pub fn with_keyword(self, keyword: &str) -> Self {
+        let keyword = keyword.to_lowercase().chars().collect::<Vec<_>>();
+        let mut order = (0..keyword.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| keyword[i]);
+        self.with_column_order(order)
+    }
+
+    pub fn encrypt(&self, input: &str) -> Result<String, CryptoSquareError> {
+        let plain = input
+            .chars()
+            .filter(|&c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        if plain.is_empty() {
+            return Ok("".into());
+        }
+
+        let (rows, columns) = self.shape(plain.len())?;
+        let order = self.order(columns)?;
+        Ok(order
+            .iter()
+            .map(|&col| {
+                (0..rows)
+                    .filter_map(|row| match plain.get(row * columns + col) {
+                        Some(&c) => Some(c),
+                        None if self.drop_padding => None,
+                        None => Some(' '),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    pub fn decrypt(&self, input: &str) -> Result<String, CryptoSquareError> {
+        let chars = input.chars().collect::<Vec<_>>();
+        let len = chars.iter().filter(|&&c| c != ' ').count();
+        if len == 0 {
+            return Ok("".into());
+        }
+
+        let (rows, columns) = self.shape(len)?;
+        let order = self.order(columns)?;
+        let column_len = |col: usize| match self.drop_padding {
+            true => (len.saturating_sub(col)).div_ceil(columns),
+            false => rows,
+        };
+        if chars.len() != order.iter().map(|&col| column_len(col)).sum::<usize>() + columns - 1 {
+            return Err(CryptoSquareError::InvalidCiphertext);
+        }
+
+        let mut grid = vec![vec![' '; columns]; rows];
+        let mut pos = 0;
+        for (k, &col) in order.iter().enumerate() {
+            if k > 0 {
+                if chars[pos] != ' ' {
+                    return Err(CryptoSquareError::InvalidCiphertext);
+                }
+                pos += 1;
+            }
+            for row in grid.iter_mut().take(column_len(col)) {
+                row[col] = chars[pos];
+                pos += 1;
+            }
+        }
+        Ok(grid.into_iter().flatten().filter(|&c| c != ' ').collect())
+    }
+
+    fn shape(&self, len: usize) -> Result<(usize, usize), CryptoSquareError> {
+        let (rows, columns) = match (self.rows, self.columns) {
+            (Some(0), _) | (_, Some(0)) => return Err(CryptoSquareError::ZeroDimension),
+            (Some(r), Some(c)) => (r, c),
+            (Some(r), None) => (r, len.div_ceil(r)),
+            (None, Some(c)) => (len.div_ceil(c), c),
+            (None, None) => {
+                let c = (len as f64).sqrt().ceil() as usize;
+                (len.div_ceil(c), c)
+            }
+        };
+        if rows * columns < len {
+            return Err(CryptoSquareError::TooSmall { rows, columns, len });
+        }
+        Ok((rows, columns))
+    }
+
+    fn order(&self, columns: usize) -> Result<Vec<usize>, CryptoSquareError> {
+        match &self.order {
+            None => Ok((0..columns).collect()),
+            Some(order) => {
+                let mut sorted = order.clone();
+                sorted.sort();
+                if sorted.len() == columns && sorted.iter().enumerate().all(|(i, &c)| i == c) {
+                    Ok(order.clone())
+                } else {
+                    Err(CryptoSquareError::InvalidOrder)
+                }
+            }
+        }
+    }
+}
+
+impl Cipher for CryptoSquare {
+    fn encrypt(&self, plaintext: &str) -> Result<String, CipherError> {
+        Ok(CryptoSquare::encrypt(self, plaintext)?)
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<String, CipherError> {
+        Ok(CryptoSquare::decrypt(self, ciphertext)?)
+    }
+}
@@ -0,0 +1,152 @@
+pub mod affine;
+pub mod analysis;
+pub mod atbash;
+pub mod crypto_square;
+pub mod rail_fence;
+pub mod rotational;
+pub mod simple;
+
+use affine::AffineCipherError;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum CipherError {
+    NotCoprime(i32),
+    InvalidKey,
+    InvalidCiphertext,
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CipherError::NotCoprime(a) => {
+                write!(f, "key `{a}` is not coprime with the alphabet size")
+            }
+            CipherError::InvalidKey => write!(f, "invalid key"),
+            CipherError::InvalidCiphertext => write!(f, "malformed ciphertext"),
+            CipherError::InvalidCharacter(c) => write!(f, "unsupported character {c:?}"),
+        }
+    }
+}
+
+impl From<AffineCipherError> for CipherError {
+    fn from(value: AffineCipherError) -> Self {
+        match value {
+            AffineCipherError::NotCoprime(a) => CipherError::NotCoprime(a),
+            AffineCipherError::CharNotInAlphabet(c) => CipherError::InvalidCharacter(c),
+            AffineCipherError::EmptyAlphabet | AffineCipherError::DuplicateChar(_) => {
+                CipherError::InvalidKey
+            }
+        }
+    }
+}
+
+/// A keyed classical cipher; the implementing value is the key.
+pub trait Cipher {
+    fn encrypt(&self, plaintext: &str) -> Result<String, CipherError>;
+    fn decrypt(&self, ciphertext: &str) -> Result<String, CipherError>;
+}
+
+/// Ciphers applied one after another on `encrypt`, and undone in reverse
+/// order on `decrypt`.
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn Cipher>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn then(mut self, cipher: impl Cipher + 'static) -> Self {
+        self.stages.push(Box::new(cipher));
+        self
+    }
+}
+
+impl Cipher for Pipeline {
+    fn encrypt(&self, plaintext: &str) -> Result<String, CipherError> {
+        self.stages
+            .iter()
+            .try_fold(plaintext.to_string(), |text, c| c.encrypt(&text))
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<String, CipherError> {
+        self.stages
+            .iter()
+            .rev()
+            .try_fold(ciphertext.to_string(), |text, c| c.decrypt(&text))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::affine::Affine;
+    use super::atbash::Atbash;
+    use super::crypto_square::CryptoSquare;
+    use super::rail_fence::RailFence;
+    use super::rotational::Rotate;
+    use super::simple::Vigenere;
+    use super::*;
+    #[test]
+    fn every_cipher_round_trips() {
+        let ciphers: Vec<Box<dyn Cipher>> = vec![
+            Box::new(Affine { a: 5, b: 7 }),
+            Box::new(Atbash),
+            Box::new(CryptoSquare::new()),
+            Box::new(RailFence::new(3)),
+            Box::new(Rotate(13)),
+            Box::new(Vigenere::new("lemon")),
+        ];
+        for cipher in ciphers {
+            let encrypted = cipher.encrypt("attackatdawn").unwrap();
+            assert_ne!(encrypted, "attackatdawn");
+            assert_eq!(cipher.decrypt(&encrypted).unwrap(), "attackatdawn");
+        }
+    }
+    #[test]
+    fn invalid_keys_are_errors() {
+        assert_eq!(
+            Affine { a: 6, b: 1 }.encrypt("abc"),
+            Err(CipherError::NotCoprime(6))
+        );
+        assert_eq!(
+            Vigenere::new("ABC").encrypt("abc"),
+            Err(CipherError::InvalidKey)
+        );
+        assert_eq!(
+            RailFence::new(0).encrypt("abc"),
+            Err(CipherError::InvalidKey)
+        );
+    }
+    #[test]
+    fn invalid_characters_are_errors() {
+        assert_eq!(
+            Vigenere::new("lemon").decrypt("abc!"),
+            Err(CipherError::InvalidCharacter('!'))
+        );
+    }
+    #[test]
+    fn affine_decrypt_ignores_case_and_punctuation() {
+        let affine = Affine { a: 5, b: 7 };
+        assert_eq!(affine.decrypt("ABC"), affine.decrypt("abc"));
+        assert_eq!(affine.decrypt("ab, 12!"), Ok("je12".to_string()));
+        assert_eq!(
+            Affine { a: 6, b: 1 }.decrypt("abc"),
+            Err(CipherError::NotCoprime(6))
+        );
+    }
+    #[test]
+    fn pipeline_rotates_then_rail_fences() {
+        let pipeline = Pipeline::new().then(Rotate(1)).then(RailFence::new(2));
+        let encrypted = pipeline.encrypt("abcdef").unwrap();
+        assert_eq!(encrypted, RailFence::new(2).encode("bcdefg"));
+        assert_eq!(pipeline.decrypt(&encrypted).unwrap(), "abcdef");
+    }
+    #[test]
+    fn empty_pipeline_is_identity() {
+        assert_eq!(Pipeline::new().encrypt("abc"), Ok("abc".to_string()));
+    }
+}
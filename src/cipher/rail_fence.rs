@@ -0,0 +1,99 @@
+use super::{Cipher, CipherError};
+
+pub struct RailFence {
+    rails: usize,
+    offset: usize,
+}
+
+impl RailFence {
+    pub fn new(rails: u32) -> RailFence {
+        RailFence {
+            rails: rails as usize,
+            offset: 0,
+        }
+    }
+
+    /// Starts the zigzag `offset` steps into its cycle instead of on the top
+    /// rail; offsets wrap around the cycle length `2 * (rails - 1)`.
+    pub fn with_offset(mut self, offset: usize) -> RailFence {
+        self.offset = offset;
+        self
+    }
+
+    pub fn encode(&self, text: &str) -> String {
+        text.chars()
+            .zip(self.indexes())
+            .fold(
+                vec![String::new(); self.rails.max(1)],
+                |mut res, (c, idx)| {
+                    res[idx].push(c);
+                    res
+                },
+            )
+            .join("")
+    }
+
+    pub fn decode(&self, cipher: &str) -> String {
+        let mut row_indexes: Vec<_> = self
+            .indexes()
+            .zip(0..)
+            .take(cipher.chars().count())
+            .collect();
+        row_indexes.sort();
+        let mut char_with_index: Vec<_> = cipher
+            .chars()
+            .zip(row_indexes)
+            .map(|(c, (_, i))| (i, c))
+            .collect();
+        char_with_index.sort();
+        char_with_index.into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Draws `text` on its rails, one line per rail, with `.` for empty cells:
+    ///
+    /// ```text
+    /// W...E...C
+    /// .E.R.D.S.
+    /// ..A...I..
+    /// ```
+    pub fn render(&self, text: &str) -> String {
+        let mut grid = vec![vec!['.'; text.chars().count()]; self.rails.max(1)];
+        for (col, (c, row)) in text.chars().zip(self.indexes()).enumerate() {
+            grid[row][col] = c;
+        }
+        grid.into_iter()
+            .map(String::from_iter)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn indexes(&self) -> impl Iterator<Item = usize> {
+        indexes(self.rails).skip(self.offset % period(self.rails))
+    }
+}
+
+/// Length of one down-and-up pass of the zigzag.
+pub fn period(rails: usize) -> usize {
+    2 * rails.max(2) - 2
+}
+
+fn indexes(n: usize) -> impl Iterator<Item = usize> {
+    let n = n.max(1);
+    (0..n).chain((1..n.saturating_sub(1)).rev()).cycle()
+}
+
+impl Cipher for RailFence {
+    fn encrypt(&self, plaintext: &str) -> Result<String, CipherError> {
+        match self.rails {
+            0 => Err(CipherError::InvalidKey),
+            _ => Ok(self.encode(plaintext)),
+        }
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<String, CipherError> {
+        match self.rails {
+            0 => Err(CipherError::InvalidKey),
+            _ => Ok(self.decode(ciphertext)),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use super::{Cipher, CipherError};
+
+pub fn rotate(input: &str, key: u8) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            'a'..='z' => char::from(((c as u8 - b'a') + key) % 26 + b'a'),
+            'A'..='Z' => char::from(((c as u8 - b'A') + key) % 26 + b'A'),
+            _ => c,
+        })
+        .collect()
+}
+
+pub struct Rotate(pub u8);
+
+impl Cipher for Rotate {
+    fn encrypt(&self, plaintext: &str) -> Result<String, CipherError> {
+        Ok(rotate(plaintext, self.0 % 26))
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<String, CipherError> {
+        Ok(rotate(ciphertext, 26 - self.0 % 26))
+    }
+}
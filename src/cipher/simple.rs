@@ -0,0 +1,113 @@
+use super::{Cipher, CipherError};
+use rand::Rng;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum SimpleCipherError {
+    EmptyKey,
+    InvalidKeyChar(char),
+    InvalidTextChar { position: usize, char: char },
+}
+
+impl From<SimpleCipherError> for CipherError {
+    fn from(value: SimpleCipherError) -> Self {
+        match value {
+            SimpleCipherError::EmptyKey | SimpleCipherError::InvalidKeyChar(_) => {
+                CipherError::InvalidKey
+            }
+            SimpleCipherError::InvalidTextChar { char, .. } => CipherError::InvalidCharacter(char),
+        }
+    }
+}
+
+pub fn encode(key: &str, s: &str) -> Option<String> {
+    Vigenere::new(key).encode(s).ok()
+}
+
+pub fn decode(key: &str, s: &str) -> Option<String> {
+    Vigenere::new(key).decode(s).ok()
+}
+
+pub fn encode_random(s: &str) -> (String, String) {
+    let key = random_key(&mut rand::rng(), 100);
+    let encoded = Vigenere::new(&key).with_pass_through().encode(s).unwrap();
+    (key, encoded)
+}
+
+pub fn random_key<R: Rng + ?Sized>(rng: &mut R, len: usize) -> String {
+    (0..len).map(|_| rng.random_range('a'..='z')).collect()
+}
+
+/// Like `encode_random`, but with a caller-supplied RNG and key length so keys
+/// can be reproduced from a seed.
+pub fn encode_random_with<R: Rng + ?Sized>(
+    rng: &mut R,
+    len: usize,
+    s: &str,
+) -> Result<(String, String), SimpleCipherError> {
+    let key = random_key(rng, len);
+    let encoded = Vigenere::new(&key).encode(s)?;
+    Ok((key, encoded))
+}
+
+pub struct Vigenere {
+    key: String,
+    pass_through: bool,
+}
+
+impl Vigenere {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            pass_through: false,
+        }
+    }
+
+    /// Leaves non-letters untouched instead of rejecting them, and shifts
+    /// uppercase letters keeping their case. Skipped characters do not use
+    /// up a key letter.
+    pub fn with_pass_through(mut self) -> Self {
+        self.pass_through = true;
+        self
+    }
+
+    pub fn encode(&self, s: &str) -> Result<String, SimpleCipherError> {
+        self.shift(s, |c, k| (c + k) % 26)
+    }
+
+    pub fn decode(&self, s: &str) -> Result<String, SimpleCipherError> {
+        self.shift(s, |c, k| (c + 26 - k) % 26)
+    }
+
+    fn shift(&self, s: &str, f: impl Fn(u8, u8) -> u8) -> Result<String, SimpleCipherError> {
+        if self.key.is_empty() {
+            return Err(SimpleCipherError::EmptyKey);
+        }
+        if let Some(k) = self.key.chars().find(|k| !k.is_ascii_lowercase()) {
+            return Err(SimpleCipherError::InvalidKeyChar(k));
+        }
+
+        let mut key = self.key.bytes().map(|k| k - b'a').cycle();
+        s.chars()
+            .enumerate()
+            .map(|(position, c)| {
+                let base = match c {
+                    'a'..='z' => b'a',
+                    'A'..='Z' if self.pass_through => b'A',
+                    _ if self.pass_through => return Ok(c),
+                    _ => return Err(SimpleCipherError::InvalidTextChar { position, char: c }),
+                };
+                Ok(char::from(f(c as u8 - base, key.next().unwrap()) + base))
+            })
+            .collect()
+    }
+}
+
+impl Cipher for Vigenere {
+    fn encrypt(&self, plaintext: &str) -> Result<String, CipherError> {
+        Ok(self.encode(plaintext)?)
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<String, CipherError> {
+        Ok(self.decode(ciphertext)?)
+    }
+}
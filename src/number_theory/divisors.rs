@@ -0,0 +1,81 @@
+use super::factor::factors;
+use itertools::Itertools;
+use std::ops::RangeInclusive;
+
+/// σ(n), the sum of all divisors of `n` including `n` itself, computed from
+/// the prime factorisation as the product of `1 + p + … + p^k`. Widened to
+/// `u128` because σ(n) can exceed `u64::MAX` for large `n`; σ(0) is 0.
+pub fn sigma(n: u64) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    factors(n)
+        .into_iter()
+        .chunk_by(|&p| p)
+        .into_iter()
+        .map(|(p, run)| {
+            let p = p as u128;
+            let mut power = 1;
+            let mut sum = 1;
+            for _ in run {
+                power *= p;
+                sum += power;
+            }
+            sum
+        })
+        .product()
+}
+
+/// s(n) = σ(n) - n, the sum of the proper divisors of `n`.
+pub fn aliquot_sum(n: u64) -> u128 {
+    sigma(n).saturating_sub(n as u128)
+}
+
+/// s(n) for every `n` in `range`, by adding each `d` to its proper multiples.
+/// Costs O(end log end) time but only O(end - start) memory.
+pub fn aliquot_sums(range: RangeInclusive<u64>) -> Vec<u64> {
+    let (start, end) = (*range.start(), *range.end());
+    if start > end {
+        return vec![];
+    }
+    let mut sums = vec![0; (end - start + 1) as usize];
+    for d in 1..=end / 2 {
+        let first = (2 * d).max(start.div_ceil(d) * d);
+        let mut m = first;
+        while m <= end {
+            sums[(m - start) as usize] += d;
+            m += d;
+        }
+    }
+    sums
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn sigma_of_small_numbers() {
+        let expected = [0, 1, 3, 4, 7, 6, 12, 8, 15, 13, 18, 12, 28];
+        for (n, &s) in expected.iter().enumerate() {
+            assert_eq!(sigma(n as u64), s, "σ({n})");
+        }
+    }
+    #[test]
+    fn sigma_of_large_numbers() {
+        assert_eq!(sigma(33_550_336), 2 * 33_550_336);
+        assert_eq!(
+            sigma(18_446_744_073_709_551_557),
+            18_446_744_073_709_551_558
+        );
+        assert!(sigma(u64::MAX - 15) > u64::MAX as u128);
+    }
+    #[test]
+    fn sieve_matches_factorisation() {
+        let sums = aliquot_sums(1_000..=2_000);
+        for (n, &s) in (1_000..=2_000).zip(&sums) {
+            assert_eq!(s as u128, aliquot_sum(n), "s({n})");
+        }
+        assert_eq!(aliquot_sums(1..=6), [0, 1, 1, 3, 1, 6]);
+        assert_eq!(aliquot_sums(0..=1), [0, 0]);
+    }
+}
@@ -0,0 +1,100 @@
+use super::modular::{gcd, mul_mod};
+use super::primality::is_prime;
+
+/// Prime factors of `n` in ascending order, with multiplicity. Small factors
+/// are trial-divided; whatever remains is split with Pollard–Brent rho.
+pub fn factors(mut n: u64) -> Vec<u64> {
+    let mut output = vec![];
+    for p in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47] {
+        while n.is_multiple_of(p) {
+            output.push(p);
+            n /= p;
+        }
+    }
+    if n > 1 {
+        split(n, &mut output);
+    }
+    output.sort();
+    output
+}
+
+fn split(n: u64, output: &mut Vec<u64>) {
+    if is_prime(n) {
+        output.push(n);
+        return;
+    }
+    let d = (1..).find_map(|c| pollard_brent(n, c)).unwrap();
+    split(d, output);
+    split(n / d, output);
+}
+
+/// Brent's variant of Pollard's rho with `f(x) = x^2 + c`, batching the gcds
+/// over products of up to `BATCH` differences. `None` means this `c` failed.
+fn pollard_brent(n: u64, c: u64) -> Option<u64> {
+    const BATCH: u64 = 128;
+    let f = |x: u64| (mul_mod(x, x, n) + c) % n;
+
+    let (mut x, mut y, mut ys) = (0, 2, 2);
+    let (mut r, mut q, mut g) = (1, 1, 1);
+    while g == 1 {
+        x = y;
+        for _ in 0..r {
+            y = f(y);
+        }
+        let mut k = 0;
+        while k < r && g == 1 {
+            ys = y;
+            for _ in 0..BATCH.min(r - k) {
+                y = f(y);
+                q = mul_mod(q, x.abs_diff(y), n);
+            }
+            g = gcd(q, n);
+            k += BATCH;
+        }
+        r *= 2;
+    }
+    if g == n {
+        // The batch overshot: step through it one difference at a time.
+        loop {
+            ys = f(ys);
+            g = gcd(x.abs_diff(ys), n);
+            if g > 1 {
+                break;
+            }
+        }
+    }
+    (g != n).then_some(g)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn small_numbers() {
+        assert_eq!(factors(1), []);
+        assert_eq!(factors(60), [2, 2, 3, 5]);
+        assert_eq!(factors(2 * 2 * 53 * 53 * 97), [2, 2, 53, 53, 97]);
+    }
+    #[test]
+    fn semiprime_of_two_32_bit_primes() {
+        assert_eq!(
+            factors(4_294_967_291 * 4_294_967_279),
+            [4_294_967_279, 4_294_967_291]
+        );
+    }
+    #[test]
+    fn large_prime_and_prime_powers() {
+        assert_eq!(
+            factors(18_446_744_073_709_551_557),
+            [18_446_744_073_709_551_557]
+        );
+        assert_eq!(factors(65_537 * 65_537 * 65_537), [65_537, 65_537, 65_537]);
+    }
+    #[test]
+    fn product_matches_input() {
+        for n in [600_851_475_143, 9_999_999_967 * 1_000_003, u64::MAX] {
+            assert_eq!(factors(n).iter().product::<u64>(), n);
+            assert!(factors(n).iter().all(|&p| is_prime(p)));
+        }
+    }
+}
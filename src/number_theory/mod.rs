@@ -0,0 +1,5 @@
+pub mod divisors;
+pub mod factor;
+pub mod modular;
+pub mod primality;
+pub mod sieve;
@@ -0,0 +1,51 @@
+/// `a * b mod m` without overflow, widening to 128 bits.
+pub fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+/// `base ^ exp mod m` by square-and-multiply; correct for every `u64` modulus.
+pub fn pow_mod(base: u64, mut exp: u64, m: u64) -> u64 {
+    if m == 1 {
+        return 0;
+    }
+    let mut power = base % m;
+    let mut acc = 1;
+    while exp != 0 {
+        if exp & 1 == 1 {
+            acc = mul_mod(acc, power, m);
+        }
+        exp >>= 1;
+        power = mul_mod(power, power, m);
+    }
+    acc
+}
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn mul_mod_does_not_overflow() {
+        let m = u64::MAX - 58;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+    }
+    #[test]
+    fn pow_mod_small_and_large() {
+        assert_eq!(pow_mod(4, 13, 497), 445);
+        assert_eq!(pow_mod(2, 0, 7), 1);
+        assert_eq!(pow_mod(5, 3, 1), 0);
+        let p = 18_446_744_073_709_551_557;
+        assert_eq!(pow_mod(3, p - 1, p), 1);
+    }
+    #[test]
+    fn gcds() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(17, 0), 17);
+    }
+}
@@ -0,0 +1,62 @@
+use super::modular::{mul_mod, pow_mod};
+
+/// Witnesses that make Miller–Rabin deterministic for every `u64`.
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    WITNESSES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn small_numbers() {
+        let primes = (0..100).filter(|&n| is_prime(n)).collect::<Vec<_>>();
+        assert_eq!(
+            primes,
+            [
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+                83, 89, 97
+            ]
+        );
+    }
+    #[test]
+    fn strong_pseudoprimes_are_rejected() {
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(3_825_123_056_546_413_051));
+        // Carmichael numbers fool Fermat but not Miller–Rabin.
+        assert!(!is_prime(561));
+        assert!(!is_prime(41_041));
+    }
+    #[test]
+    fn large_primes() {
+        assert!(is_prime(4_294_967_291));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(18_446_744_073_709_551_557 - 2));
+        assert!(!is_prime(4_294_967_291 * 4_294_967_279));
+    }
+}
@@ -0,0 +1,247 @@
+/// Odd numbers per segment: one bit each, so a segment's bitset is 32 KiB and
+/// fits in L1 cache.
+const SEGMENT_ODDS: usize = 32 * 1024 * 8;
+
+/// Primes up to `limit` with a plain odd-only sieve; used for the sieving
+/// primes, which only need to reach the square root of the range.
+fn small_primes(limit: u64) -> Vec<u64> {
+    if limit < 2 {
+        return vec![];
+    }
+    let limit = limit as usize;
+    let mut composite = vec![false; limit / 2 + 1];
+    let mut primes = vec![2];
+    for i in 1..composite.len() {
+        let p = 2 * i + 1;
+        if p > limit {
+            break;
+        }
+        if composite[i] {
+            continue;
+        }
+        primes.push(p as u64);
+        (p * p / 2..composite.len())
+            .step_by(p)
+            .for_each(|j| composite[j] = true);
+    }
+    primes
+}
+
+/// Sieves the `SEGMENT_ODDS` odd numbers starting at the odd number `low`.
+/// Bit `i` of the result is set when `low + 2 * i` is composite (or one).
+fn sieve_segment(low: u64, base_primes: &[u64]) -> Vec<u64> {
+    let high = low + 2 * SEGMENT_ODDS as u64;
+    let mut bits = vec![0u64; SEGMENT_ODDS / 64];
+    if low == 1 {
+        bits[0] |= 1;
+    }
+    for &p in base_primes.iter().skip(1) {
+        if p * p >= high {
+            break;
+        }
+        let mut start = (p * p).max(low.div_ceil(p) * p);
+        if start % 2 == 0 {
+            start += p;
+        }
+        let mut i = ((start - low) / 2) as usize;
+        while i < SEGMENT_ODDS {
+            bits[i / 64] |= 1 << (i % 64);
+            i += p as usize;
+        }
+    }
+    bits
+}
+
+fn segment_primes(low: u64, bits: &[u64]) -> impl Iterator<Item = u64> + '_ {
+    bits.iter().enumerate().flat_map(move |(w, &word)| {
+        let mut free = !word;
+        std::iter::from_fn(move || {
+            if free == 0 {
+                return None;
+            }
+            let bit = free.trailing_zeros() as u64;
+            free &= free - 1;
+            Some(low + 2 * (w as u64 * 64 + bit))
+        })
+    })
+}
+
+/// An unbounded iterator over the primes, sieving one segment at a time and
+/// growing the set of sieving primes as the segments move up.
+pub struct Primes {
+    base_primes: Vec<u64>,
+    base_limit: u64,
+    low: u64,
+    buffer: Vec<u64>,
+    pos: usize,
+}
+
+impl Primes {
+    pub fn new() -> Self {
+        Self {
+            base_primes: vec![],
+            base_limit: 0,
+            low: 1,
+            buffer: vec![2],
+            pos: 0,
+        }
+    }
+
+    fn next_segment(&mut self) {
+        let high = self.low + 2 * SEGMENT_ODDS as u64;
+        if self.base_limit * self.base_limit < high {
+            self.base_limit = (high as f64).sqrt() as u64 * 2;
+            self.base_primes = small_primes(self.base_limit);
+        }
+        let bits = sieve_segment(self.low, &self.base_primes);
+        self.buffer = segment_primes(self.low, &bits).collect();
+        self.pos = 0;
+        self.low = high;
+    }
+}
+
+impl Default for Primes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.pos == self.buffer.len() {
+            self.next_segment();
+        }
+        self.pos += 1;
+        Some(self.buffer[self.pos - 1])
+    }
+}
+
+/// Walks the segments covering `3..=n`, handing each bitset to `f`.
+fn for_each_segment(n: u64, mut f: impl FnMut(u64, &[u64])) {
+    let base_primes = small_primes((n as f64).sqrt() as u64 + 1);
+    let mut low = 1;
+    while low <= n {
+        f(low, &sieve_segment(low, &base_primes));
+        low += 2 * SEGMENT_ODDS as u64;
+    }
+}
+
+pub fn primes_up_to(n: u64) -> Vec<u64> {
+    if n < 2 {
+        return vec![];
+    }
+    let mut primes = vec![2];
+    for_each_segment(n, |low, bits| {
+        primes.extend(segment_primes(low, bits).take_while(|&p| p <= n))
+    });
+    primes
+}
+
+/// π(n), the number of primes not exceeding `n`, counted straight from the
+/// segment bitsets.
+pub fn prime_count(n: u64) -> u64 {
+    if n < 2 {
+        return 0;
+    }
+    let mut count = 1;
+    for_each_segment(n, |low, bits| {
+        let odds = ((n - low) / 2 + 1).min(SEGMENT_ODDS as u64) as usize;
+        let (full, rest) = (odds / 64, odds % 64);
+        count += bits[..full]
+            .iter()
+            .map(|w| w.count_zeros() as u64)
+            .sum::<u64>();
+        if rest > 0 {
+            count += (!bits[full] & ((1 << rest) - 1)).count_ones() as u64;
+        }
+    });
+    count
+}
+
+/// Upper bound on the `k`-th prime (1-indexed), from Rosser's theorem
+/// `p_k < k (ln k + ln ln k)` for `k >= 6`.
+fn nth_prime_upper_bound(k: u64) -> u64 {
+    if k < 6 {
+        return 15;
+    }
+    let k = k as f64;
+    (k * (k.ln() + k.ln().ln())).ceil() as u64
+}
+
+/// The `n`-th prime counting from zero (`nth_prime(0) == 2`). The bound from
+/// the prime-counting estimate sizes the sieving primes; segments are then
+/// counted by popcount until the one holding the answer.
+pub fn nth_prime(n: u64) -> u64 {
+    if n == 0 {
+        return 2;
+    }
+    let bound = nth_prime_upper_bound(n + 1);
+    let base_primes = small_primes((bound as f64).sqrt() as u64 + 1);
+    let mut remaining = n;
+    let mut low = 1;
+    loop {
+        let bits = sieve_segment(low, &base_primes);
+        let count = bits.iter().map(|w| w.count_zeros() as u64).sum::<u64>();
+        if count >= remaining {
+            return segment_primes(low, &bits)
+                .nth(remaining as usize - 1)
+                .unwrap();
+        }
+        remaining -= count;
+        low += 2 * SEGMENT_ODDS as u64;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    fn naive(n: u64) -> Vec<u64> {
+        (2..=n)
+            .filter(|&k| (2..k).take_while(|d| d * d <= k).all(|d| k % d != 0))
+            .collect()
+    }
+    #[test]
+    fn small_ranges_match_trial_division() {
+        for n in [0, 1, 2, 3, 4, 10, 97, 100, 1000] {
+            assert_eq!(primes_up_to(n), naive(n));
+        }
+    }
+    #[test]
+    fn primes_across_segment_boundaries() {
+        let n = 2 * SEGMENT_ODDS as u64 + 5000;
+        assert_eq!(primes_up_to(n), naive(n));
+    }
+    #[test]
+    fn prime_counts() {
+        assert_eq!(prime_count(1), 0);
+        assert_eq!(prime_count(2), 1);
+        assert_eq!(prime_count(10), 4);
+        assert_eq!(prime_count(1_000_000), 78_498);
+        assert_eq!(prime_count(10_000_000), 664_579);
+    }
+    #[test]
+    fn prime_count_at_segment_edges() {
+        for n in [2 * SEGMENT_ODDS as u64 - 1, 2 * SEGMENT_ODDS as u64 + 1] {
+            assert_eq!(prime_count(n), primes_up_to(n).len() as u64);
+        }
+    }
+    #[test]
+    fn iterator_is_unbounded() {
+        let primes = Primes::new();
+        assert_eq!(
+            primes.take(10).collect::<Vec<_>>(),
+            [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]
+        );
+        assert_eq!(Primes::new().nth(100_000), Some(1_299_721));
+    }
+    #[test]
+    fn nth_primes() {
+        assert_eq!(nth_prime(0), 2);
+        assert_eq!(nth_prime(1), 3);
+        assert_eq!(nth_prime(5), 13);
+        assert_eq!(nth_prime(10_000), 104_743);
+        assert_eq!(nth_prime(1_000_000), 15_485_867);
+    }
+}